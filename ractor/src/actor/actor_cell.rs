@@ -16,7 +16,7 @@ use std::sync::Arc;
 use futures::FutureExt;
 
 use super::actor_properties::MuxedMessage;
-use super::messages::{Signal, StopMessage};
+use super::messages::{Signal, StopMessage, StopReason};
 use super::SupervisionEvent;
 use crate::actor::actor_properties::ActorProperties;
 use crate::concurrency::{MpscUnboundedReceiver as InputPortReceiver, OneshotReceiver};
@@ -47,6 +47,15 @@ pub enum ActorStatus {
     Stopped = 6u8,
 }
 
+/// [ShutdownOutcome] describes how an actor was shut down by [ActorCell::stop_or_kill]
+#[derive(Debug, Clone, Eq, PartialEq, Copy)]
+pub enum ShutdownOutcome {
+    /// The actor drained its message queue and stopped gracefully
+    Drained,
+    /// The actor didn't finish draining in time and was killed
+    Killed,
+}
+
 /// Actor states where operations can continue to interact with an agent
 pub const ACTIVE_STATES: [ActorStatus; 3] = [
     ActorStatus::Starting,
@@ -140,6 +149,67 @@ impl ActorPortSet {
         }
     }
 
+    /// Collect the supervision events arriving within `window` of the first event
+    /// into a single batch. The signal port can interrupt the collection, and a stop
+    /// request closes the window early.
+    ///
+    /// * `first` - The first [SupervisionEvent] of the batch, which opened the window
+    /// * `window` - The amount of time to collect further events for
+    ///
+    /// Returns [Ok(`Vec<SupervisionEvent>`)] with the batch in arrival order along with
+    /// the [StopMessage] which closed the window (if any), [Err(Signal)] in the event the
+    /// signal interrupts the collection.
+    pub(crate) async fn collect_supervision_batch(
+        &mut self,
+        first: SupervisionEvent,
+        window: crate::concurrency::Duration,
+    ) -> Result<(Vec<SupervisionEvent>, Option<StopMessage>), Signal> {
+        let supervisor_rx = &mut self.supervisor_rx;
+        let stop_rx = &mut self.stop_rx;
+        let collect = async move {
+            let deadline = crate::concurrency::Instant::now() + window;
+            let mut batch = vec![first];
+            loop {
+                let remaining =
+                    deadline.saturating_duration_since(crate::concurrency::Instant::now());
+                let next = crate::concurrency::timeout(remaining, supervisor_rx.recv());
+                match futures::future::select(&mut *stop_rx, Box::pin(next)).await {
+                    // a stop request closes the window, the batch is handled before stopping.
+                    // If the stop port closed instead, the next port listen will observe it
+                    futures::future::Either::Left((stop, _)) => return (batch, stop.ok()),
+                    futures::future::Either::Right((Ok(Some(evt)), _)) => batch.push(evt),
+                    // the window elapsed, or the channel closed (which the next
+                    // port listen will observe)
+                    futures::future::Either::Right(_) => break,
+                }
+            }
+            (batch, None)
+        };
+
+        #[cfg(feature = "async-std")]
+        {
+            crate::concurrency::select! {
+                signal = (&mut self.signal_rx).fuse() => {
+                    Err(signal.unwrap_or(Signal::Kill))
+                }
+                batch = collect.fuse() => {
+                    Ok(batch)
+                }
+            }
+        }
+        #[cfg(not(feature = "async-std"))]
+        {
+            crate::concurrency::select! {
+                signal = &mut self.signal_rx => {
+                    Err(signal.unwrap_or(Signal::Kill))
+                }
+                batch = collect => {
+                    Ok(batch)
+                }
+            }
+        }
+    }
+
     /// List to the input ports in priority. The priority of listening for messages is
     /// 1. Signal port
     /// 2. Stop port
@@ -226,13 +296,20 @@ impl ActorCell {
     /// Construct a new [ActorCell] pointing to an [super::Actor] and return the message reception channels as a [ActorPortSet]
     ///
     /// * `name` - Optional name for the actor
+    /// * `register_name` - Whether to register the name in the registry. Replacements leave it
+    ///   out, so it can be handed over once they've started (see [ActorCell::hot_restart])
     ///
     /// Returns a tuple [(ActorCell, ActorPortSet)] to bootstrap the [crate::Actor]
-    pub(crate) fn new<TActor>(name: Option<ActorName>) -> Result<(Self, ActorPortSet), SpawnErr>
+    pub(crate) fn new<TActor>(
+        name: Option<ActorName>,
+        register_name: bool,
+    ) -> Result<(Self, ActorPortSet), SpawnErr>
     where
         TActor: Actor,
     {
+        crate::actor::actor_count::ACTOR_COUNT.acquire()?;
         let (props, rx1, rx2, rx3, rx4) = ActorProperties::new::<TActor>(name.clone());
+        props.set_counted();
         let cell = Self {
             inner: Arc::new(props),
         };
@@ -243,8 +320,13 @@ impl ActorCell {
             crate::registry::pid_registry::register_pid(cell.get_id(), cell.clone())?;
         }
 
-        if let Some(r_name) = name {
-            crate::registry::register(r_name, cell.clone())?;
+        if let Some(r_name) = name.filter(|_| register_name) {
+            if let Err(err) = crate::registry::register(r_name, cell.clone()) {
+                // drop every other reference to the cell, so it releases its actor count
+                #[cfg(feature = "cluster")]
+                crate::registry::pid_registry::unregister_pid(cell.get_id());
+                return Err(err.into());
+            }
         }
 
         Ok((
@@ -307,6 +389,193 @@ impl ActorCell {
         self.inner.get_status()
     }
 
+    /// Determine if the [super::Actor] is ready to serve traffic. Unless the actor defers
+    /// its readiness (see [super::Actor::defer_readiness]) this is the same as being
+    /// [ActorStatus::Running]. Actors which defer readiness are only ready once they're
+    /// running and have called [ActorCell::mark_ready].
+    ///
+    /// Returns [true] if the actor is ready, [false] otherwise
+    pub fn is_ready(&self) -> bool {
+        self.inner.is_ready()
+    }
+
+    /// Determine if the [super::Actor] isn't withholding its readiness, regardless of its
+    /// status. Unlike [ActorCell::is_ready] this is [true] for an actor which is still
+    /// starting, unless it defers its readiness (see [super::Actor::defer_readiness]).
+    pub(crate) fn is_marked_ready(&self) -> bool {
+        self.inner.is_marked_ready()
+    }
+
+    /// Mark the [super::Actor] as ready to serve traffic, typically once its warmup (loading
+    /// caches, establishing connections, etc) is complete. Only meaningful for actors which
+    /// defer their readiness (see [super::Actor::defer_readiness]), others are ready as soon
+    /// as they're running. Messages sent before the actor is ready still queue in its mailbox.
+    ///
+    /// NOTE: This is meant to be called by the actor itself, through the `myself` handle
+    /// passed to its handlers. Any holder of an [ActorCell] or [super::actor_ref::ActorRef]
+    /// can call it, but external callers must not, as they can't know whether the actor's
+    /// warmup is complete.
+    pub fn mark_ready(&self) {
+        self.set_ready(true);
+    }
+
+    /// Set the readiness flag of the [super::Actor]
+    ///
+    /// * `ready` - Whether the actor is ready once it's running
+    pub(crate) fn set_ready(&self, ready: bool) {
+        self.inner.set_ready(ready);
+    }
+
+    /// Wait for the [super::Actor] to stop, without asking it to
+    pub(crate) async fn wait_for_exit(&self) {
+        self.inner.wait_for_exit().await
+    }
+
+    /// Wait for the [super::Actor] to become ready (see [ActorCell::is_ready])
+    ///
+    /// Returns [true] once the actor is ready, or [false] if the actor stopped before
+    /// becoming ready
+    pub async fn wait_for_ready(&self) -> bool {
+        self.inner.wait_for_ready().await
+    }
+
+    /// Retrieve the name of the message the [super::Actor] is currently handling, if
+    /// any (see [super::Actor::message_name]). This is helpful when debugging an actor
+    /// which is [ActorStatus::Running] but not making progress, as it shows what the
+    /// actor is stuck processing.
+    ///
+    /// Returns [Some(String)] with the message's name while a message is being
+    /// handled, [None] if the actor is idle (or not handling a regular message)
+    pub fn current_message(&self) -> Option<String> {
+        self.inner.get_current_message().map(String::from)
+    }
+
+    /// Mark the message which the [super::Actor] is presently handling
+    ///
+    /// * `message` - The name of the in-flight message, or [None] to clear it
+    pub(crate) fn set_current_message(&self, message: Option<&'static str>) {
+        self.inner.set_current_message(message)
+    }
+
+    /// Retrieve the total weight of the messages currently queued in this actor's mailbox
+    /// (see [super::Actor::message_weight] and [super::Actor::max_total_mailbox_bytes])
+    ///
+    /// Returns the total weight, which is always 0 for remote actors and for actors without a
+    /// byte budget since their messages aren't weighed
+    pub fn mailbox_bytes(&self) -> usize {
+        self.inner.get_mailbox_bytes()
+    }
+
+    /// Retrieve a summary of the latencies of the messages this actor has handled, when it
+    /// has opted in with [super::Actor::track_handling_latency]
+    ///
+    /// Returns [Some(LatencySnapshot)] with the latency percentiles if latency tracking is
+    /// enabled for this actor, [None] otherwise (including for remote actors)
+    #[cfg(feature = "latency_histogram")]
+    pub fn latency_snapshot(&self) -> Option<crate::LatencySnapshot> {
+        self.inner
+            .get_latency_histogram()
+            .map(|histogram| histogram.snapshot())
+    }
+
+    /// Start recording the handling latency of this actor's messages
+    #[cfg(feature = "latency_histogram")]
+    pub(crate) fn enable_latency_tracking(&self) {
+        self.inner.enable_latency_tracking()
+    }
+
+    /// Record the handling latency of a message, if latency tracking is enabled
+    ///
+    /// * `latency` - The time taken to handle the message
+    #[cfg(feature = "latency_histogram")]
+    pub(crate) fn record_handling_latency(&self, latency: crate::concurrency::Duration) {
+        if let Some(histogram) = self.inner.get_latency_histogram() {
+            histogram.record(latency);
+        }
+    }
+
+    /// Wait until this actor's mailbox has room in its byte budget (see
+    /// [super::Actor::max_total_mailbox_bytes]). Upstream producers can await this before
+    /// sending, to slow down when the actor falls behind. See [super::actor_ref::ActorRef::send_when_ready]
+    /// for sending a specific message once it fits.
+    ///
+    /// Returns [true] once the queued messages weigh less than the budget (immediately for
+    /// actors without a budget), [false] if the actor has stopped accepting messages
+    pub async fn wait_for_mailbox_capacity(&self) -> bool {
+        self.inner.wait_for_mailbox_capacity().await
+    }
+
+    /// Send a strongly-typed message, waiting for room in the mailbox's byte budget when it's
+    /// full rather than failing with [MessagingErr::MailboxBudgetExceeded]
+    ///
+    /// * `message` - The message to send
+    ///
+    /// Returns [Ok(())] once the message is sent, [Err(MessagingErr)] if the send failed
+    /// (including [MessagingErr::MailboxBudgetExceeded] if the message alone exceeds the
+    /// whole budget, so it could never fit)
+    pub async fn send_message_when_ready<TMessage>(
+        &self,
+        message: TMessage,
+    ) -> Result<(), MessagingErr<TMessage>>
+    where
+        TMessage: Message,
+    {
+        self.inner.send_message_when_ready(message).await
+    }
+
+    /// Set the byte budget of this actor's mailbox
+    ///
+    /// * `budget` - The maximum total weight of queued messages, [None] for no budget
+    pub(crate) fn set_mailbox_byte_budget(&self, budget: Option<usize>) {
+        self.inner.set_mailbox_byte_budget(budget)
+    }
+
+    /// Release the weight of a message which has left the mailbox from the byte budget
+    ///
+    /// * `weight` - The weight of the message
+    pub(crate) fn release_mailbox_bytes(&self, weight: usize) {
+        self.inner.release_mailbox_bytes(weight)
+    }
+
+    /// Store a value in this actor's local scratch space, keyed by its type. Actor-locals
+    /// hold framework or middleware state (tracing spans, request context, etc) which is
+    /// accessible from any handler via `myself`, without being part of the actor's
+    /// [super::Actor::State].
+    ///
+    /// Locals live for the lifetime of the actor and are cleared when it stops.
+    ///
+    /// * `value` - The value to store, replacing any existing value of the same type
+    ///
+    /// Returns the previously stored value of type `T`, if any
+    pub fn set_local<T>(&self, value: T) -> Option<T>
+    where
+        T: std::any::Any + Send + Sync,
+    {
+        self.inner.set_local(value)
+    }
+
+    /// Retrieve a copy of the value of type `T` from this actor's local scratch space
+    /// (see [ActorCell::set_local])
+    ///
+    /// Returns [Some(T)] if a value of type `T` is stored, [None] otherwise
+    pub fn get_local<T>(&self) -> Option<T>
+    where
+        T: std::any::Any + Send + Sync + Clone,
+    {
+        self.inner.get_local()
+    }
+
+    /// Remove the value of type `T` from this actor's local scratch space
+    /// (see [ActorCell::set_local])
+    ///
+    /// Returns the removed value, if one was stored
+    pub fn remove_local<T>(&self) -> Option<T>
+    where
+        T: std::any::Any + Send + Sync,
+    {
+        self.inner.remove_local()
+    }
+
     /// Identifies if this actor supports remote (dist) communication
     ///
     /// Returns [true] if the actor's messaging protocols support remote calls, [false] otherwise
@@ -333,21 +602,38 @@ impl ActorCell {
             }
             // If it's enrolled in the registry, remove it
             if let Some(name) = self.get_name() {
-                crate::registry::unregister(name);
+                crate::registry::unregister(name, self.get_id());
             }
             // Leave all + stop monitoring pg groups (if any)
             crate::pg::demonitor_all(self.get_id());
             crate::pg::leave_all(self.get_id());
         }
 
+        // The status is stored before notifying, so listeners which check it after binding
+        // the stop notification never miss the stop
+        self.inner.set_status(status);
+
         // Fix for #254. We should only notify the stop listener AFTER post_stop
         // has executed, which is when the state gets set to `Stopped`.
         if status == ActorStatus::Stopped {
+            // actor-locals only live as long as the actor
+            self.inner.clear_locals();
+            self.inner.release_count();
             // notify whoever might be waiting on the stop signal
             self.inner.notify_stop_listener();
         }
 
-        self.inner.set_status(status)
+        // readiness depends on the status, so waiters need to re-evaluate it
+        if matches!(
+            status,
+            ActorStatus::Running | ActorStatus::Stopping | ActorStatus::Stopped
+        ) {
+            self.inner.notify_ready_listeners();
+        }
+        // senders waiting for mailbox capacity need to give up once messages are rejected
+        if status >= ActorStatus::Draining {
+            self.inner.notify_capacity_listeners();
+        }
     }
 
     /// Terminate this [super::Actor] and all it's children
@@ -420,8 +706,17 @@ impl ActorCell {
 
     /// Stop this [super::Actor] gracefully (stopping message processing)
     ///
-    /// * `reason` - An optional string reason why the stop is occurring
+    /// * `reason` - An optional string reason why the stop is occurring, which is
+    ///   reported as [StopReason::Custom]
     pub fn stop(&self, reason: Option<String>) {
+        self.stop_with_reason(reason.map(StopReason::from))
+    }
+
+    /// Stop this [super::Actor] gracefully (stopping message processing), with a
+    /// structured reason
+    ///
+    /// * `reason` - An optional [StopReason] why the stop is occurring
+    pub fn stop_with_reason(&self, reason: Option<StopReason>) {
         // ignore failures, since that means the actor is dead already
         let _ = self.inner.send_stop(reason);
     }
@@ -429,7 +724,8 @@ impl ActorCell {
     /// Stop the [super::Actor] gracefully (stopping messaging processing)
     /// and wait for the actor shutdown to complete
     ///
-    /// * `reason` - An optional string reason why the stop is occurring
+    /// * `reason` - An optional string reason why the stop is occurring, which is
+    ///   reported as [StopReason::Custom]
     /// * `timeout` - An optional timeout duration to wait for shutdown to occur
     ///
     /// Returns [Ok(())] upon the actor being stopped/shutdown. [Err(RactorErr::Messaging(_))] if the channel is closed
@@ -439,6 +735,22 @@ impl ActorCell {
         &self,
         reason: Option<String>,
         timeout: Option<crate::concurrency::Duration>,
+    ) -> Result<(), RactorErr<StopMessage>> {
+        self.stop_and_wait_with_reason(reason.map(StopReason::from), timeout)
+            .await
+    }
+
+    /// Stop the [super::Actor] gracefully (stopping messaging processing), with a
+    /// structured reason, and wait for the actor shutdown to complete
+    ///
+    /// * `reason` - An optional [StopReason] why the stop is occurring
+    /// * `timeout` - An optional timeout duration to wait for shutdown to occur
+    ///
+    /// Returns the same as [ActorCell::stop_and_wait]
+    pub async fn stop_and_wait_with_reason(
+        &self,
+        reason: Option<StopReason>,
+        timeout: Option<crate::concurrency::Duration>,
     ) -> Result<(), RactorErr<StopMessage>> {
         if let Some(to) = timeout {
             match crate::concurrency::timeout(to, self.inner.send_stop_and_wait(reason)).await {
@@ -479,6 +791,71 @@ impl ActorCell {
         self.inner.send_message::<TMessage>(message)
     }
 
+    /// Send a strongly-typed message, which is only constructed if the actor can receive
+    /// it. Liveness is checked before invoking the builder, so no work is wasted building
+    /// messages for dead actors (e.g. when broadcasting over a stale routing table).
+    ///
+    /// NOTE: The check is racy, the actor can still die between the check and the send,
+    /// in which case the built message is returned in [MessagingErr::SendErr]. The
+    /// mailbox's byte budget is also only checked once the message is built.
+    ///
+    /// * `builder` - Constructs the message to send
+    ///
+    /// Returns [Ok(())] on successful message send, [Err(MessagingErr::ChannelClosed)] if
+    /// the actor isn't accepting messages (and the builder wasn't invoked), or another
+    /// [Err(MessagingErr)] if the send failed
+    pub fn send_with<TMessage, F>(&self, builder: F) -> Result<(), MessagingErr<TMessage>>
+    where
+        TMessage: Message,
+        F: FnOnce() -> TMessage,
+    {
+        self.inner.send_message_with(builder)
+    }
+
+    /// Temporarily redirect all messages sent to this actor to another actor. Senders
+    /// holding this actor's [ActorCell] (or [super::actor_ref::ActorRef]) are unaware of
+    /// the redirect, which allows migrating or performing maintenance on an actor without
+    /// informing everyone who may message it. The redirect stays in place until
+    /// [ActorCell::clear_redirect] is called.
+    ///
+    /// Only regular messages are redirected. Signals, stop requests, supervision events,
+    /// and drain markers are still delivered to this actor.
+    ///
+    /// Type safety: the target must handle the same message type as this actor, since
+    /// messages are forwarded as-is. This is checked here and the redirect is rejected with
+    /// [MessagingErr::InvalidActorType] if the types differ. The message type of a remote
+    /// actor can't be checked, so redirecting to a remote actor is rejected the same way.
+    ///
+    /// Liveness: while redirected, messages are delivered to the target regardless of this
+    /// actor's state, so this actor may even be stopped. If the target is not alive, the
+    /// send fails with the target's error and the message is handed back to the sender;
+    /// it does *not* fall back to this actor.
+    ///
+    /// Redirects chain. If the target is itself redirected, messages follow that redirect
+    /// too, so an actor which was hot restarted several times (see [ActorCell::hot_restart])
+    /// still reaches the latest replacement. Redirects which would lead back to this actor
+    /// (including redirecting an actor to itself) are rejected with
+    /// [MessagingErr::RedirectCycle].
+    ///
+    /// Serialized messages from remote senders (see [ActorCell::send_serialized]) are
+    /// redirected too.
+    ///
+    /// * `other` - The actor to forward messages to
+    ///
+    /// Returns [Ok(())] if the redirect was installed, [Err(MessagingErr)] otherwise
+    pub fn redirect_to(&self, other: ActorCell) -> Result<(), MessagingErr<()>> {
+        if !other.get_id().is_local() || self.get_type_id() != other.get_type_id() {
+            return Err(MessagingErr::InvalidActorType);
+        }
+        self.inner.set_redirect(other.inner)
+    }
+
+    /// Remove any redirect installed with [ActorCell::redirect_to], such that messages are
+    /// again delivered to this actor
+    pub fn clear_redirect(&self) {
+        self.inner.clear_redirect();
+    }
+
     /// Drain the actor's message queue and when finished processing, terminate the actor.
     ///
     /// Any messages received after the drain marker but prior to shutdown will be rejected
@@ -507,6 +884,121 @@ impl ActorCell {
         }
     }
 
+    /// Gracefully shut down the actor, escalating to a kill if it takes too long.
+    ///
+    /// The actor is first drained (see [ActorCell::drain]), processing the messages
+    /// remaining in its queue. If it hasn't stopped within `drain_timeout`, it's
+    /// killed (see [ActorCell::kill]) which terminates any in-progress async work.
+    ///
+    /// * `drain_timeout`: The amount of time to wait for the drain to complete before
+    ///   killing the actor
+    ///
+    /// Returns [Ok(ShutdownOutcome)] denoting whether the actor drained or had to be killed,
+    /// [Err(RactorErr::Messaging(_))] if the actor couldn't be drained (e.g. it's already stopped)
+    pub async fn stop_or_kill(
+        &self,
+        drain_timeout: crate::concurrency::Duration,
+    ) -> Result<ShutdownOutcome, RactorErr<()>> {
+        match self.drain_and_wait(Some(drain_timeout)).await {
+            Ok(()) => Ok(ShutdownOutcome::Drained),
+            Err(RactorErr::Timeout) => match self.kill_and_wait(None).await {
+                Ok(()) => Ok(ShutdownOutcome::Killed),
+                // the actor finished draining between the deadline and the kill
+                Err(RactorErr::Messaging(MessagingErr::ChannelClosed)) => {
+                    Ok(ShutdownOutcome::Drained)
+                }
+                Err(other) => Err(other),
+            },
+            Err(other) => Err(other),
+        }
+    }
+
+    /// Replace this actor with a fresh instance without dropping traffic (a "hot restart").
+    ///
+    /// The replacement is spawned and, once it has started, takes over this actor's registered
+    /// name, its supervisor, and its children. Then this actor's incoming messages are redirected
+    /// (see [ActorCell::redirect_to]) to the replacement, and finally this actor is drained
+    /// and stopped. Senders holding this actor's [ActorCell] keep reaching the replacement
+    /// since the redirect outlives this actor, and redirects chain across repeated hot
+    /// restarts. Sends can still fail for the usual reasons once they reach the
+    /// replacement, for example if the replacement itself has stopped.
+    ///
+    /// The replacement must handle the same message type as this actor, otherwise
+    /// [MessagingErr::InvalidActorType] is returned and nothing is changed.
+    ///
+    /// Consistency window: messages which were already queued at this actor before the
+    /// redirect are still processed by this actor while it drains, concurrently with the
+    /// replacement handling newly sent messages. Therefore ordering across the handover is
+    /// not guaranteed, and any state must be handed over through `startup_args` as this
+    /// actor's state isn't transferred. Process group memberships and monitors aren't
+    /// transferred either.
+    ///
+    /// This actor keeps its name while the replacement runs `pre_start`, so name lookups
+    /// (see [crate::registry::where_is]) keep finding it and the name can't be taken by
+    /// another actor. The name is swapped over to the replacement in a single registry
+    /// update once it has started. If this actor stops during the replacement's startup,
+    /// its name is released and the replacement claims it, unless another actor registered
+    /// the name in the meantime. In that case the replacement runs without being registered.
+    ///
+    /// * `handler` - The [Actor] defining the logic of the replacement
+    /// * `startup_args` - Arguments passed to the replacement's `pre_start`
+    /// * `drain_timeout` - The amount of time this actor has to drain before being killed
+    ///
+    /// Returns [Ok((ActorRef, JoinHandle<()>))] of the replacement upon a successful
+    /// handover, [Err(RactorErr)] if the replacement couldn't be started, in which case
+    /// this actor is left untouched.
+    pub async fn hot_restart<TActor>(
+        &self,
+        handler: TActor,
+        startup_args: TActor::Arguments,
+        drain_timeout: crate::concurrency::Duration,
+    ) -> Result<
+        (
+            crate::ActorRef<TActor::Msg>,
+            crate::concurrency::JoinHandle<()>,
+        ),
+        RactorErr<()>,
+    >
+    where
+        TActor: Actor,
+    {
+        if self.get_type_id() != TypeId::of::<TActor::Msg>() {
+            return Err(MessagingErr::InvalidActorType.into());
+        }
+
+        // the replacement carries the name, but only registers it once it has started
+        let name = self.get_name();
+        let supervisor = self.inner.tree.get_supervisor();
+        let (replacement, handle) = crate::actor::ActorRuntime::spawn_replacement(
+            name.clone(),
+            handler,
+            startup_args,
+            supervisor.clone(),
+        )
+        .await?;
+        if let Some(name) = name {
+            if !crate::registry::hand_over(name.clone(), self.get_id(), replacement.get_cell()) {
+                tracing::warn!(
+                    "Actor name '{name}' was taken while {:?} was being replaced, the replacement isn't registered",
+                    self.get_id()
+                );
+            }
+        }
+
+        // unlink from the supervisor, so this actor's exit isn't treated as a failure,
+        // and re-parent the children
+        if let Some(sup) = supervisor {
+            self.unlink(sup);
+        }
+        self.inner.tree.transfer_children(&replacement.get_cell());
+
+        self.redirect_to(replacement.get_cell())?;
+        // the actor may have already exited, in which case there's nothing to drain
+        let _ = self.stop_or_kill(drain_timeout).await;
+
+        Ok((replacement, handle))
+    }
+
     /// Send a serialized binary message to the actor.
     ///
     /// * `message` - The message to send
@@ -533,18 +1025,31 @@ impl ActorCell {
     /// Stop any children of this actor, not waiting for their exit, and threading
     /// the optional reason to all children
     ///
-    /// * `reason`: The stop reason to send to all the children
+    /// * `reason`: The stop reason to send to all the children, which is reported as
+    ///   [StopReason::Custom]
     ///
     /// This swallows and communication errors because if you can't send a message
     /// to the child, it's dropped the message channel, and is dead/stopped already.
     pub fn stop_children(&self, reason: Option<String>) {
+        self.stop_children_with_reason(reason.map(StopReason::from));
+    }
+
+    /// Stop any children of this actor, not waiting for their exit, and threading
+    /// the optional structured reason to all children
+    ///
+    /// * `reason`: The [StopReason] to send to all the children, e.g. [StopReason::Supervisor]
+    ///
+    /// This swallows and communication errors because if you can't send a message
+    /// to the child, it's dropped the message channel, and is dead/stopped already.
+    pub fn stop_children_with_reason(&self, reason: Option<StopReason>) {
         self.inner.tree.stop_all_children(reason);
     }
 
     /// Stop any children of this actor, and wait for their collective exit, optionally
     /// threading the optional reason to all children
     ///
-    /// * `reason`: The stop reason to send to all the children
+    /// * `reason`: The stop reason to send to all the children, which is reported as
+    ///   [StopReason::Custom]
     /// * `timeout`: An optional timeout which is the maximum time to wait for the actor stop
     ///   operation to complete
     ///
@@ -554,6 +1059,24 @@ impl ActorCell {
         &self,
         reason: Option<String>,
         timeout: Option<crate::concurrency::Duration>,
+    ) {
+        self.stop_children_and_wait_with_reason(reason.map(StopReason::from), timeout)
+            .await
+    }
+
+    /// Stop any children of this actor, and wait for their collective exit, optionally
+    /// threading the optional structured reason to all children
+    ///
+    /// * `reason`: The [StopReason] to send to all the children, e.g. [StopReason::Supervisor]
+    /// * `timeout`: An optional timeout which is the maximum time to wait for the actor stop
+    ///   operation to complete
+    ///
+    /// This swallows and communication errors because if you can't send a message
+    /// to the child, it's dropped the message channel, and is dead/stopped already.
+    pub async fn stop_children_and_wait_with_reason(
+        &self,
+        reason: Option<StopReason>,
+        timeout: Option<crate::concurrency::Duration>,
     ) {
         self.inner
             .tree
@@ -620,4 +1143,9 @@ impl ActorCell {
     pub(crate) fn get_num_parents(&self) -> usize {
         self.inner.tree.get_num_parents()
     }
+
+    #[cfg(test)]
+    pub(crate) fn get_supervisor(&self) -> Option<ActorCell> {
+        self.inner.tree.get_supervisor()
+    }
 }
@@ -0,0 +1,115 @@
+// Copyright (c) Sean Lawlor
+//
+// This source code is licensed under both the MIT license found in the
+// LICENSE-MIT file in the root directory of this source tree.
+
+//! Process-wide accounting of live actors, with an optional cap
+//!
+//! Every local actor counts towards the process-wide total from the moment it's created
+//! until it stops. Remote actors (proxies for actors on other nodes) aren't counted.
+//!
+//! The cap set with [set_max_actors] is a global guardrail against runaway actor creation,
+//! e.g. a bug spawning actors in a loop or unbounded input-driven spawning. Once the cap is
+//! reached, spawning fails with [crate::SpawnErr::ActorLimitReached]. Hitting the cap in
+//! practice most likely means actors are leaking, or that actors created on demand are
+//! missing a policy to stop them once idle, rather than that the cap should be raised.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::SpawnErr;
+
+/// A count of live actors with an optional cap
+pub(crate) struct ActorCounter {
+    count: AtomicUsize,
+    // usize::MAX denotes no cap
+    max: AtomicUsize,
+}
+
+impl ActorCounter {
+    pub(crate) const fn new() -> Self {
+        Self {
+            count: AtomicUsize::new(0),
+            max: AtomicUsize::new(usize::MAX),
+        }
+    }
+
+    pub(crate) fn set_max(&self, max: Option<usize>) {
+        self.max.store(max.unwrap_or(usize::MAX), Ordering::SeqCst);
+    }
+
+    pub(crate) fn count(&self) -> usize {
+        self.count.load(Ordering::SeqCst)
+    }
+
+    /// Count a new actor, if the cap allows it
+    ///
+    /// Returns [Ok(())] if the actor was counted, [Err(SpawnErr::ActorLimitReached)] if
+    /// the cap has been reached
+    pub(crate) fn acquire(&self) -> Result<(), SpawnErr> {
+        let max = self.max.load(Ordering::SeqCst);
+        self.count
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |count| {
+                (count < max).then_some(count + 1)
+            })
+            .map(|_| ())
+            .map_err(|_| SpawnErr::ActorLimitReached(max))
+    }
+
+    /// Stop counting an actor
+    pub(crate) fn release(&self) {
+        let _ = self
+            .count
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |count| {
+                Some(count.saturating_sub(1))
+            });
+    }
+}
+
+/// The process-wide count of live local actors
+pub(crate) static ACTOR_COUNT: ActorCounter = ActorCounter::new();
+
+/// Set the process-wide cap on live actors. Spawns which would exceed the cap fail with
+/// [SpawnErr::ActorLimitReached]. Lowering the cap below the current count doesn't stop
+/// any actors, but no new actors can be spawned until enough have stopped.
+///
+/// * `max` - The maximum number of live actors, [None] to remove the cap (the default)
+pub fn set_max_actors(max: Option<usize>) {
+    ACTOR_COUNT.set_max(max);
+}
+
+/// Retrieve the number of live local actors in this process
+pub fn current_actor_count() -> usize {
+    ACTOR_COUNT.count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ActorCounter;
+    use crate::SpawnErr;
+
+    #[test]
+    fn test_actor_counter_cap() {
+        let counter = ActorCounter::new();
+        counter.set_max(Some(2));
+        assert!(counter.acquire().is_ok());
+        assert!(counter.acquire().is_ok());
+        assert!(matches!(
+            counter.acquire(),
+            Err(SpawnErr::ActorLimitReached(2))
+        ));
+        assert_eq!(2, counter.count());
+
+        // stopping an actor frees room for another
+        counter.release();
+        assert!(counter.acquire().is_ok());
+
+        // lowering the cap doesn't affect live actors
+        counter.set_max(Some(1));
+        assert_eq!(2, counter.count());
+        assert!(counter.acquire().is_err());
+
+        counter.set_max(None);
+        assert!(counter.acquire().is_ok());
+        assert_eq!(3, counter.count());
+    }
+}
@@ -3,10 +3,12 @@
 // This source code is licensed under both the MIT license found in the
 // LICENSE-MIT file in the root directory of this source tree.
 
-use std::sync::atomic::{AtomicU8, Ordering};
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::atomic::{fence, AtomicBool, AtomicPtr, AtomicU8, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 
-use crate::actor::messages::StopMessage;
+use crate::actor::messages::{StopMessage, StopReason};
 use crate::actor::supervision::SupervisionTree;
 use crate::concurrency::{
     MpscUnboundedReceiver as InputPortReceiver, MpscUnboundedSender as InputPort, OneshotReceiver,
@@ -31,14 +33,108 @@ pub(crate) struct ActorProperties {
     pub(crate) name: Option<ActorName>,
     status: Arc<AtomicU8>,
     wait_handler: Arc<mpsc::Notify>,
+    ready: AtomicBool,
+    // whether the actor is included in the process-wide actor count
+    counted: AtomicBool,
+    ready_handler: mpsc::Notify,
+    capacity_handler: mpsc::Notify,
+    // the number of senders waiting on `capacity_handler`
+    capacity_waiters: AtomicUsize,
     pub(crate) signal: Mutex<Option<OneshotInputPort<Signal>>>,
     pub(crate) stop: Mutex<Option<OneshotInputPort<StopMessage>>>,
     pub(crate) supervision: InputPort<SupervisionEvent>,
     pub(crate) message: InputPort<MuxedMessage>,
     pub(crate) tree: SupervisionTree,
-    pub(crate) type_id: std::any::TypeId,
+    pub(crate) type_id: TypeId,
     #[cfg(feature = "cluster")]
     pub(crate) supports_remoting: bool,
+    current_message: MessageNameSlot,
+    redirect: Mutex<Option<Arc<ActorProperties>>>,
+    // whether `redirect` is set, so sends to actors which aren't redirected (nearly all of
+    // them) don't contend on its lock
+    redirected: AtomicBool,
+    locals: Mutex<HashMap<TypeId, Box<dyn Any + Send + Sync>>>,
+    weigh_message: fn(&dyn Any) -> usize,
+    mailbox_bytes: AtomicUsize,
+    // usize::MAX denotes no budget
+    max_mailbox_bytes: AtomicUsize,
+    #[cfg(feature = "latency_histogram")]
+    latency: once_cell::sync::OnceCell<crate::actor::latency::LatencyHistogram>,
+}
+
+/// Registers a sender as waiting for mailbox capacity for as long as it's held, see
+/// [ActorProperties::release_mailbox_bytes]
+struct CapacityWaiter<'a>(&'a AtomicUsize);
+
+impl<'a> CapacityWaiter<'a> {
+    fn new(props: &'a ActorProperties) -> Self {
+        props.capacity_waiters.fetch_add(1, Ordering::SeqCst);
+        Self(&props.capacity_waiters)
+    }
+}
+
+impl Drop for CapacityWaiter<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Holds the name of the message an actor is handling, see
+/// [ActorProperties::set_current_message]. It's written for every handled message, so
+/// it's lock-free: the name's pointer and length are stored separately, and a sequence
+/// number (odd while a write is in progress) lets readers detect and retry a read which
+/// raced a write. There's only ever one writer, the actor's processing loop.
+#[derive(Default)]
+struct MessageNameSlot {
+    seq: AtomicUsize,
+    ptr: AtomicPtr<u8>,
+    len: AtomicUsize,
+}
+
+impl MessageNameSlot {
+    fn set(&self, name: Option<&'static str>) {
+        let (ptr, len) = name.map_or((std::ptr::null_mut(), 0), |name| {
+            (name.as_ptr() as *mut u8, name.len())
+        });
+        let seq = self.seq.load(Ordering::Relaxed);
+        self.seq.store(seq.wrapping_add(1), Ordering::Relaxed);
+        fence(Ordering::Release);
+        self.ptr.store(ptr, Ordering::Relaxed);
+        self.len.store(len, Ordering::Relaxed);
+        self.seq.store(seq.wrapping_add(2), Ordering::Release);
+    }
+
+    fn get(&self) -> Option<&'static str> {
+        loop {
+            let seq = self.seq.load(Ordering::Acquire);
+            let ptr = self.ptr.load(Ordering::Relaxed);
+            let len = self.len.load(Ordering::Relaxed);
+            fence(Ordering::Acquire);
+            if seq % 2 == 1 || self.seq.load(Ordering::Relaxed) != seq {
+                std::hint::spin_loop();
+                continue;
+            }
+            if ptr.is_null() {
+                return None;
+            }
+            // SAFETY: the sequence number didn't change while reading, so `ptr` and `len`
+            // were stored together by `set`, from the same `&'static str`
+            return Some(unsafe {
+                std::str::from_utf8_unchecked(std::slice::from_raw_parts(ptr, len))
+            });
+        }
+    }
+}
+
+/// Held while installing a redirect, see [ActorProperties::set_redirect]
+static REDIRECT_INSTALL: Mutex<()> = Mutex::new(());
+
+/// Compute the weight of a type-erased message for the given actor type
+fn weigh_message<TActor: Actor>(message: &dyn Any) -> usize {
+    message
+        .downcast_ref::<TActor::Msg>()
+        .map(TActor::message_weight)
+        .unwrap_or(0)
 }
 
 impl ActorProperties {
@@ -81,13 +177,27 @@ impl ActorProperties {
                 status: Arc::new(AtomicU8::new(ActorStatus::Unstarted as u8)),
                 signal: Mutex::new(Some(tx_signal)),
                 wait_handler: Arc::new(mpsc::Notify::new()),
+                ready: AtomicBool::new(true),
+                counted: AtomicBool::new(false),
+                ready_handler: mpsc::Notify::new(),
+                capacity_handler: mpsc::Notify::new(),
+                capacity_waiters: AtomicUsize::new(0),
                 stop: Mutex::new(Some(tx_stop)),
                 supervision: tx_supervision,
                 message: tx_message,
                 tree: SupervisionTree::default(),
-                type_id: std::any::TypeId::of::<TActor::Msg>(),
+                type_id: TypeId::of::<TActor::Msg>(),
                 #[cfg(feature = "cluster")]
                 supports_remoting: TActor::Msg::serializable(),
+                current_message: MessageNameSlot::default(),
+                redirect: Mutex::new(None),
+                redirected: AtomicBool::new(false),
+                locals: Mutex::new(HashMap::new()),
+                weigh_message: weigh_message::<TActor>,
+                mailbox_bytes: AtomicUsize::new(0),
+                max_mailbox_bytes: AtomicUsize::new(usize::MAX),
+                #[cfg(feature = "latency_histogram")]
+                latency: once_cell::sync::OnceCell::new(),
             },
             rx_signal,
             rx_stop,
@@ -112,6 +222,262 @@ impl ActorProperties {
         self.status.store(status as u8, Ordering::SeqCst);
     }
 
+    /// Mark the actor as included in the process-wide actor count, which it has
+    /// already been counted towards
+    pub(crate) fn set_counted(&self) {
+        self.counted.store(true, Ordering::SeqCst);
+    }
+
+    /// Remove the actor from the process-wide actor count, if it's still counted
+    pub(crate) fn release_count(&self) {
+        if self.counted.swap(false, Ordering::SeqCst) {
+            crate::actor::actor_count::ACTOR_COUNT.release();
+        }
+    }
+
+    pub(crate) fn set_ready(&self, ready: bool) {
+        self.ready.store(ready, Ordering::SeqCst);
+        self.notify_ready_listeners();
+    }
+
+    pub(crate) fn is_marked_ready(&self) -> bool {
+        self.ready.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn is_ready(&self) -> bool {
+        self.ready.load(Ordering::SeqCst) && self.get_status() == ActorStatus::Running
+    }
+
+    /// Wait for the actor to become ready
+    ///
+    /// Returns [true] once the actor is ready, [false] if it stopped before becoming ready
+    pub(crate) async fn wait_for_ready(&self) -> bool {
+        loop {
+            // bind the notification before checking, so a change mid-check isn't missed
+            let rx = self.ready_handler.notified();
+            if self.is_ready() {
+                return true;
+            }
+            if self.get_status() >= ActorStatus::Stopping {
+                return false;
+            }
+            rx.await;
+        }
+    }
+
+    pub(crate) fn notify_ready_listeners(&self) {
+        self.ready_handler.notify_waiters();
+    }
+
+    pub(crate) fn get_current_message(&self) -> Option<&'static str> {
+        self.current_message.get()
+    }
+
+    pub(crate) fn set_current_message(&self, message: Option<&'static str>) {
+        self.current_message.set(message);
+    }
+
+    /// Redirect this actor's messages to `target`, rejecting redirects which would lead
+    /// back to this actor. Redirects hold a strong reference to their target, so a cycle
+    /// would also keep every actor in it alive forever.
+    pub(crate) fn set_redirect(
+        &self,
+        target: Arc<ActorProperties>,
+    ) -> Result<(), MessagingErr<()>> {
+        // installs are serialized so two concurrent redirects (a->b, b->a) can't each
+        // miss the other and close a cycle
+        let _guard = REDIRECT_INSTALL.lock().unwrap();
+        let mut hop = Some(target.clone());
+        while let Some(next) = hop {
+            if std::ptr::eq(next.as_ref(), self) {
+                return Err(MessagingErr::RedirectCycle);
+            }
+            hop = next.next_redirect();
+        }
+        let mut redirect = self.redirect.lock().unwrap();
+        *redirect = Some(target);
+        self.redirected.store(true, Ordering::Release);
+        Ok(())
+    }
+
+    pub(crate) fn clear_redirect(&self) {
+        let mut redirect = self.redirect.lock().unwrap();
+        *redirect = None;
+        self.redirected.store(false, Ordering::Release);
+    }
+
+    /// The actor this actor is directly redirected to, if any
+    fn next_redirect(&self) -> Option<Arc<ActorProperties>> {
+        if !self.redirected.load(Ordering::Acquire) {
+            return None;
+        }
+        self.redirect.lock().unwrap().clone()
+    }
+
+    /// Resolve where this actor's messages should be delivered, following the redirect
+    /// chain to its end. Returns [None] if this actor isn't redirected. Chains are finite
+    /// since [ActorProperties::set_redirect] rejects cycles.
+    fn redirect_target(&self) -> Option<Arc<ActorProperties>> {
+        let mut target = self.next_redirect()?;
+        loop {
+            match target.next_redirect() {
+                Some(next) => target = next,
+                None => return Some(target),
+            }
+        }
+    }
+
+    pub(crate) fn set_local<T>(&self, value: T) -> Option<T>
+    where
+        T: Any + Send + Sync,
+    {
+        self.locals
+            .lock()
+            .unwrap()
+            .insert(TypeId::of::<T>(), Box::new(value))
+            .and_then(|old| old.downcast::<T>().ok())
+            .map(|old| *old)
+    }
+
+    pub(crate) fn get_local<T>(&self) -> Option<T>
+    where
+        T: Any + Send + Sync + Clone,
+    {
+        self.locals
+            .lock()
+            .unwrap()
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_ref::<T>())
+            .cloned()
+    }
+
+    pub(crate) fn remove_local<T>(&self) -> Option<T>
+    where
+        T: Any + Send + Sync,
+    {
+        self.locals
+            .lock()
+            .unwrap()
+            .remove(&TypeId::of::<T>())
+            .and_then(|old| old.downcast::<T>().ok())
+            .map(|old| *old)
+    }
+
+    pub(crate) fn clear_locals(&self) {
+        self.locals.lock().unwrap().clear();
+    }
+
+    pub(crate) fn set_mailbox_byte_budget(&self, budget: Option<usize>) {
+        self.max_mailbox_bytes
+            .store(budget.unwrap_or(usize::MAX), Ordering::SeqCst);
+    }
+
+    pub(crate) fn get_mailbox_bytes(&self) -> usize {
+        self.mailbox_bytes.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn release_mailbox_bytes(&self, weight: usize) {
+        if weight == 0 {
+            return;
+        }
+        let _ = self
+            .mailbox_bytes
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |current| {
+                Some(current.saturating_sub(weight))
+            });
+        // Waiters register before checking the budget, so if none are registered here any
+        // later waiter will see the freed capacity. This keeps the common case of nobody
+        // waiting to a single atomic load per dequeued message.
+        if self.capacity_waiters.load(Ordering::SeqCst) > 0 {
+            self.notify_capacity_listeners();
+        }
+    }
+
+    pub(crate) fn notify_capacity_listeners(&self) {
+        self.capacity_handler.notify_waiters();
+    }
+
+    /// Wait for the mailbox to have room in its byte budget
+    ///
+    /// Returns [true] once the queued messages weigh less than the budget, [false] if the
+    /// actor stopped accepting messages
+    pub(crate) async fn wait_for_mailbox_capacity(&self) -> bool {
+        let _waiting = CapacityWaiter::new(self);
+        loop {
+            // bind the notification before checking, so capacity freed mid-check isn't missed
+            let rx = self.capacity_handler.notified();
+            if self.get_status() >= ActorStatus::Draining {
+                return false;
+            }
+            if self.get_mailbox_bytes() < self.max_mailbox_bytes.load(Ordering::SeqCst) {
+                return true;
+            }
+            rx.await;
+        }
+    }
+
+    /// Send a message, waiting for room in the target mailbox's byte budget rather than
+    /// rejecting the message when the budget is exceeded
+    pub(crate) async fn send_message_when_ready<TMessage>(
+        &self,
+        message: TMessage,
+    ) -> Result<(), MessagingErr<TMessage>>
+    where
+        TMessage: Message,
+    {
+        let mut message = message;
+        loop {
+            let redirect = self.redirect_target();
+            let target = redirect.as_deref().unwrap_or(self);
+            // bind the notification before sending, so capacity freed mid-send isn't missed
+            let _waiting = CapacityWaiter::new(target);
+            let rx = target.capacity_handler.notified();
+            match target.enqueue_message(message) {
+                Err(MessagingErr::MailboxBudgetExceeded(returned)) => {
+                    // a message which exceeds the whole budget will never fit
+                    let weight = (target.weigh_message)(&returned);
+                    if weight > target.max_mailbox_bytes.load(Ordering::SeqCst) {
+                        return Err(MessagingErr::MailboxBudgetExceeded(returned));
+                    }
+                    message = returned;
+                    rx.await;
+                }
+                other => return other,
+            }
+        }
+    }
+
+    #[cfg(feature = "latency_histogram")]
+    pub(crate) fn enable_latency_tracking(&self) {
+        let _ = self
+            .latency
+            .set(crate::actor::latency::LatencyHistogram::new());
+    }
+
+    #[cfg(feature = "latency_histogram")]
+    pub(crate) fn get_latency_histogram(&self) -> Option<&crate::actor::latency::LatencyHistogram> {
+        self.latency.get()
+    }
+
+    /// Reserve room in the mailbox's byte budget for a message. The message is only
+    /// weighed if the mailbox has a budget, so actors without one don't pay for it.
+    ///
+    /// Returns [Some] with the weight actually reserved, which is 0 if the mailbox has no
+    /// budget and nothing is accounted, or [None] if the weight doesn't fit in the budget
+    fn reserve_mailbox_bytes(&self, message: &dyn Any) -> Option<usize> {
+        let max = self.max_mailbox_bytes.load(Ordering::SeqCst);
+        if max == usize::MAX {
+            return Some(0);
+        }
+        let weight = (self.weigh_message)(message);
+        self.mailbox_bytes
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |current| {
+                current.checked_add(weight).filter(|total| *total <= max)
+            })
+            .ok()
+            .map(|_| weight)
+    }
+
     pub(crate) fn send_signal(&self, signal: Signal) -> Result<(), MessagingErr<()>> {
         self.signal
             .lock()
@@ -133,12 +499,47 @@ impl ActorProperties {
         &self,
         message: TMessage,
     ) -> Result<(), MessagingErr<TMessage>>
+    where
+        TMessage: Message,
+    {
+        // If redirected, hand the message directly to the mailbox at the end of the
+        // redirect chain
+        let redirect = self.redirect_target();
+        match redirect {
+            Some(target) => target.enqueue_message(message),
+            None => self.enqueue_message(message),
+        }
+    }
+
+    pub(crate) fn send_message_with<TMessage, F>(
+        &self,
+        builder: F,
+    ) -> Result<(), MessagingErr<TMessage>>
+    where
+        TMessage: Message,
+        F: FnOnce() -> TMessage,
+    {
+        let redirect = self.redirect_target();
+        let target = redirect.as_deref().unwrap_or(self);
+
+        // Check everything which can be checked without the message before building it
+        if target.id.is_local() && target.type_id != TypeId::of::<TMessage>() {
+            return Err(MessagingErr::InvalidActorType);
+        }
+        if target.get_status() >= ActorStatus::Draining || target.message.is_closed() {
+            return Err(MessagingErr::ChannelClosed);
+        }
+
+        target.enqueue_message(builder())
+    }
+
+    fn enqueue_message<TMessage>(&self, message: TMessage) -> Result<(), MessagingErr<TMessage>>
     where
         TMessage: Message,
     {
         // Only type-check messages of local actors, remote actors send serialized
         // payloads
-        if self.id.is_local() && self.type_id != std::any::TypeId::of::<TMessage>() {
+        if self.id.is_local() && self.type_id != TypeId::of::<TMessage>() {
             return Err(MessagingErr::InvalidActorType);
         }
 
@@ -148,14 +549,32 @@ impl ActorProperties {
             return Err(MessagingErr::SendErr(message));
         }
 
-        let boxed = message
-            .box_message(&self.id)
-            .map_err(|_e| MessagingErr::InvalidActorType)?;
+        // Only local actors account for the weight of their messages, remote actors
+        // receive serialized payloads
+        let weight = if self.id.is_local() {
+            match self.reserve_mailbox_bytes(&message) {
+                Some(weight) => weight,
+                None => return Err(MessagingErr::MailboxBudgetExceeded(message)),
+            }
+        } else {
+            0
+        };
+
+        let mut boxed = message.box_message(&self.id).map_err(|_e| {
+            self.release_mailbox_bytes(weight);
+            MessagingErr::InvalidActorType
+        })?;
+        boxed.weight = weight;
         self.message
             .send(MuxedMessage::Message(boxed))
-            .map_err(|e| match e.0 {
-                MuxedMessage::Message(m) => MessagingErr::SendErr(TMessage::from_boxed(m).unwrap()),
-                _ => panic!("Expected a boxed message but got a drain message"),
+            .map_err(|e| {
+                self.release_mailbox_bytes(weight);
+                match e.0 {
+                    MuxedMessage::Message(m) => {
+                        MessagingErr::SendErr(TMessage::from_boxed(m).unwrap())
+                    }
+                    _ => panic!("Expected a boxed message but got a drain message"),
+                }
             })
     }
 
@@ -178,7 +597,7 @@ impl ActorProperties {
     pub(crate) async fn drain_and_wait(&self) -> Result<(), MessagingErr<()>> {
         let rx = self.wait_handler.notified();
         self.drain()?;
-        rx.await;
+        self.wait_for_stop(rx).await;
         Ok(())
     }
 
@@ -187,12 +606,16 @@ impl ActorProperties {
         &self,
         message: SerializedMessage,
     ) -> Result<(), MessagingErr<SerializedMessage>> {
+        let redirect = self.redirect_target();
+        let target = redirect.as_deref().unwrap_or(self);
         let boxed = BoxedMessage {
             msg: None,
             serialized_msg: Some(message),
             span: None,
+            weight: 0,
         };
-        self.message
+        target
+            .message
             .send(MuxedMessage::Message(boxed))
             .map_err(|e| match e.0 {
                 MuxedMessage::Message(m) => MessagingErr::SendErr(m.serialized_msg.unwrap()),
@@ -202,7 +625,7 @@ impl ActorProperties {
 
     pub(crate) fn send_stop(
         &self,
-        reason: Option<String>,
+        reason: Option<StopReason>,
     ) -> Result<(), MessagingErr<StopMessage>> {
         let msg = reason.map(StopMessage::Reason).unwrap_or(StopMessage::Stop);
         self.stop
@@ -217,11 +640,11 @@ impl ActorProperties {
     /// Send the stop signal, threading in a OneShot sender which notifies when the shutdown is completed
     pub(crate) async fn send_stop_and_wait(
         &self,
-        reason: Option<String>,
+        reason: Option<StopReason>,
     ) -> Result<(), MessagingErr<StopMessage>> {
         let rx = self.wait_handler.notified();
         self.send_stop(reason)?;
-        rx.await;
+        self.wait_for_stop(rx).await;
         Ok(())
     }
 
@@ -232,12 +655,71 @@ impl ActorProperties {
     ) -> Result<(), MessagingErr<()>> {
         // first bind the wait handler
         let rx = self.wait_handler.notified();
-        let _ = self.send_signal(signal);
+        let sent = self.send_signal(signal);
+        if self.get_status() == ActorStatus::Stopped {
+            // the actor stopped on its own before the signal could take effect, so the
+            // stop notification has already fired
+            return sent;
+        }
+        // even if the signal couldn't be delivered, the actor is already shutting down
         rx.await;
         Ok(())
     }
 
+    /// Wait for the actor to stop, without asking it to
+    pub(crate) async fn wait_for_exit(&self) {
+        let rx = self.wait_handler.notified();
+        self.wait_for_stop(rx).await;
+    }
+
+    /// Wait for the stop notification bound by `rx`, unless the actor has already stopped
+    /// (and therefore already fired the notification)
+    async fn wait_for_stop(&self, rx: impl std::future::Future<Output = ()>) {
+        if self.get_status() != ActorStatus::Stopped {
+            rx.await;
+        }
+    }
+
     pub(crate) fn notify_stop_listener(&self) {
         self.wait_handler.notify_waiters();
     }
 }
+
+impl Drop for ActorProperties {
+    fn drop(&mut self) {
+        // actors which never ran (and therefore never stopped) still need releasing
+        self.release_count();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_name_slot_reads_whole_names() {
+        let slot = Arc::new(MessageNameSlot::default());
+        assert_eq!(None, slot.get());
+
+        let writer = {
+            let slot = slot.clone();
+            std::thread::spawn(move || {
+                for i in 0..100_000 {
+                    slot.set(match i % 3 {
+                        0 => Some("short"),
+                        1 => Some("a considerably longer name"),
+                        _ => None,
+                    });
+                }
+            })
+        };
+        // a read racing a write never observes a pointer and length from different names
+        while !writer.is_finished() {
+            assert!(matches!(
+                slot.get(),
+                None | Some("short") | Some("a considerably longer name")
+            ));
+        }
+        writer.join().unwrap();
+    }
+}
@@ -7,7 +7,7 @@
 
 use std::marker::PhantomData;
 
-use crate::{ActorName, Message, MessagingErr, SupervisionEvent};
+use crate::{ActorId, ActorName, Message, MessagingErr, SupervisionEvent, ACTIVE_STATES};
 
 use super::ActorCell;
 
@@ -90,6 +90,56 @@ where
         self.inner.send_message::<TMessage>(message)
     }
 
+    /// Send a strongly-typed message, which is only constructed if the actor can receive
+    /// it. See [ActorCell::send_with] for the liveness check and its caveats.
+    ///
+    /// * `builder` - Constructs the message to send
+    ///
+    /// Returns [Ok(())] on successful message send, [Err(MessagingErr::ChannelClosed)] if
+    /// the actor isn't accepting messages (and the builder wasn't invoked), or another
+    /// [Err(MessagingErr)] if the send failed
+    pub fn send_with<F>(&self, builder: F) -> Result<(), MessagingErr<TMessage>>
+    where
+        F: FnOnce() -> TMessage,
+    {
+        self.inner.send_with(builder)
+    }
+
+    /// Send a strongly-typed message once the actor's mailbox has room for it in its byte
+    /// budget (see [crate::Actor::max_total_mailbox_bytes]), rather than failing with
+    /// [MessagingErr::MailboxBudgetExceeded].
+    ///
+    /// In a pipeline of actors (A -> B -> C), a stage which forwards with `send_when_ready`
+    /// stalls while the next stage is full, so its own mailbox fills up in turn and
+    /// backpressure propagates upstream to the source.
+    ///
+    /// NOTE: Backpressure must not form a cycle. An actor awaiting room in its own mailbox
+    /// (directly, or through a cycle of stages awaiting each other) deadlocks, since the
+    /// blocked handler is what drains the mailbox. Feedback to upstream stages should use
+    /// [ActorRef::send_message] (or a mailbox without a budget) to break the cycle.
+    ///
+    /// * `message` - The message to send
+    ///
+    /// Returns [Ok(())] once the message is sent, [Err(MessagingErr)] if the send failed
+    /// (including [MessagingErr::MailboxBudgetExceeded] if the message alone exceeds the
+    /// whole budget, so it could never fit)
+    pub async fn send_when_ready(&self, message: TMessage) -> Result<(), MessagingErr<TMessage>> {
+        self.inner
+            .send_message_when_ready::<TMessage>(message)
+            .await
+    }
+
+    /// Build a capability-limited [ActorSender] for this actor. The sender can only
+    /// send `TMessage`s to the actor, it cannot stop, kill, or otherwise manage it.
+    ///
+    /// Returns a new [ActorSender] which forwards messages to this actor
+    pub fn sender(&self) -> ActorSender<TMessage> {
+        ActorSender {
+            inner: self.inner.clone(),
+            _tactor: PhantomData,
+        }
+    }
+
     // ========================== General Actor Operation Aliases ========================== //
 
     // -------------------------- ActorRegistry -------------------------- //
@@ -108,3 +158,59 @@ where
         None
     }
 }
+
+/// An [ActorSender] is a send-only handle to an actor. Unlike an [ActorRef]
+/// it doesn't expose the underlying [ActorCell], so the holder can only send
+/// `TMessage`s to the actor and can't stop, kill, link, or otherwise manage it.
+///
+/// This is helpful when handing a handle to an actor across module boundaries
+/// where the receiving component should only be able to submit work.
+pub struct ActorSender<TMessage> {
+    inner: ActorCell,
+    _tactor: PhantomData<fn() -> TMessage>,
+}
+
+impl<TMessage> Clone for ActorSender<TMessage> {
+    fn clone(&self) -> Self {
+        ActorSender {
+            inner: self.inner.clone(),
+            _tactor: PhantomData,
+        }
+    }
+}
+
+impl<TMessage> std::fmt::Debug for ActorSender<TMessage> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ActorSender")
+            .field("name", &self.inner.get_name())
+            .field("id", &self.inner.get_id())
+            .finish()
+    }
+}
+
+impl<TMessage> ActorSender<TMessage>
+where
+    TMessage: Message,
+{
+    /// Retrieve the [ActorId] of the actor this sender forwards to
+    pub fn get_id(&self) -> ActorId {
+        self.inner.get_id()
+    }
+
+    /// Identifies if the actor this sender forwards to can still accept messages
+    ///
+    /// Returns [true] if the actor is in one of the [ACTIVE_STATES], [false] otherwise
+    pub fn is_alive(&self) -> bool {
+        ACTIVE_STATES.contains(&self.inner.get_status())
+    }
+
+    /// Send a strongly-typed message to the actor
+    ///
+    /// * `message` - The message to send
+    ///
+    /// Returns [Ok(())] on successful message send, [Err(MessagingErr::SendErr)] containing
+    /// the message if the actor is dead (or draining), [Err(MessagingErr)] otherwise
+    pub fn send(&self, message: TMessage) -> Result<(), MessagingErr<TMessage>> {
+        self.inner.send_message::<TMessage>(message)
+    }
+}
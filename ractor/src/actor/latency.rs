@@ -0,0 +1,211 @@
+// Copyright (c) Sean Lawlor
+//
+// This source code is licensed under both the MIT license found in the
+// LICENSE-MIT file in the root directory of this source tree.
+
+//! Per-actor message handling latency histograms
+//!
+//! Available with the `latency_histogram` feature. Actors opt in individually with
+//! [crate::Actor::track_handling_latency], after which the time spent in each completed
+//! `handle` call is recorded and a summary can be read with
+//! [crate::ActorCell::latency_snapshot].
+//!
+//! ## Buckets
+//!
+//! Latencies are recorded in microseconds into log-linear buckets, similar to an HDR
+//! histogram with 2 significant bits. Latencies under 4us each have their own bucket,
+//! and above that every power-of-two range is split into 4 equally sized sub-buckets,
+//! so a reported percentile over-estimates the true latency by at most 25%. The last
+//! power-of-two range starts at 2^31us (~36 minutes), and its largest bucket starts at
+//! 7 * 2^29us (~63 minutes). Any longer latency is recorded into that largest bucket.
+//!
+//! ## Memory cost
+//!
+//! Each histogram holds [NUM_BUCKETS] atomic counters plus 3 atomic summary values,
+//! roughly 1KiB per tracked actor. Recording is lock-free: a clock read plus a few
+//! relaxed atomic increments per message. A [LatencySnapshot] copies the bucket
+//! counters.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::concurrency::Duration;
+
+/// The number of linear sub-buckets per power-of-two range
+const SUB_BUCKETS: u64 = 4;
+/// The largest power of two (in microseconds) with its own range of buckets
+const MAX_EXPONENT: u64 = 31;
+/// The number of buckets in a latency histogram
+pub const NUM_BUCKETS: usize = (SUB_BUCKETS + (MAX_EXPONENT - 1) * SUB_BUCKETS) as usize;
+
+/// Find the bucket index for a latency in microseconds
+fn bucket_index(micros: u64) -> usize {
+    if micros < SUB_BUCKETS {
+        return micros as usize;
+    }
+    // latencies past the last range all land in the largest bucket
+    if micros >= 1 << (MAX_EXPONENT + 1) {
+        return NUM_BUCKETS - 1;
+    }
+    let exponent = 63 - u64::from(micros.leading_zeros());
+    let shift = exponent - 2;
+    let sub = (micros >> shift) & (SUB_BUCKETS - 1);
+    (SUB_BUCKETS + shift * SUB_BUCKETS + sub) as usize
+}
+
+/// The exclusive upper bound of a bucket, in microseconds. The largest bucket is unbounded
+fn bucket_upper_bound(index: usize) -> u64 {
+    if index == NUM_BUCKETS - 1 {
+        return u64::MAX;
+    }
+    let index = index as u64;
+    if index < SUB_BUCKETS {
+        return index + 1;
+    }
+    let offset = index - SUB_BUCKETS;
+    let shift = offset / SUB_BUCKETS;
+    let sub = offset % SUB_BUCKETS;
+    (SUB_BUCKETS + sub + 1) << shift
+}
+
+/// A lock-free histogram of message handling latencies
+#[derive(Debug)]
+pub(crate) struct LatencyHistogram {
+    buckets: Box<[AtomicU64]>,
+    count: AtomicU64,
+    sum_micros: AtomicU64,
+    max_micros: AtomicU64,
+}
+
+impl LatencyHistogram {
+    pub(crate) fn new() -> Self {
+        Self {
+            buckets: (0..NUM_BUCKETS).map(|_| AtomicU64::new(0)).collect(),
+            count: AtomicU64::new(0),
+            sum_micros: AtomicU64::new(0),
+            max_micros: AtomicU64::new(0),
+        }
+    }
+
+    /// Record a single handling latency
+    pub(crate) fn record(&self, latency: Duration) {
+        let micros = u64::try_from(latency.as_micros()).unwrap_or(u64::MAX);
+        self.buckets[bucket_index(micros)].fetch_add(1, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.sum_micros.fetch_add(micros, Ordering::Relaxed);
+        self.max_micros.fetch_max(micros, Ordering::Relaxed);
+    }
+
+    /// Take a point-in-time copy of the histogram
+    pub(crate) fn snapshot(&self) -> LatencySnapshot {
+        LatencySnapshot {
+            buckets: self
+                .buckets
+                .iter()
+                .map(|bucket| bucket.load(Ordering::Relaxed))
+                .collect(),
+            count: self.count.load(Ordering::Relaxed),
+            sum_micros: self.sum_micros.load(Ordering::Relaxed),
+            max_micros: self.max_micros.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// A point-in-time summary of an actor's message handling latencies
+#[derive(Debug, Clone)]
+pub struct LatencySnapshot {
+    buckets: Vec<u64>,
+    count: u64,
+    sum_micros: u64,
+    max_micros: u64,
+}
+
+impl LatencySnapshot {
+    /// The number of messages whose handling latency was recorded
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// The mean handling latency
+    pub fn mean(&self) -> Duration {
+        if self.count == 0 {
+            return Duration::ZERO;
+        }
+        Duration::from_micros(self.sum_micros / self.count)
+    }
+
+    /// The largest handling latency recorded (exact, not bucketed)
+    pub fn max(&self) -> Duration {
+        Duration::from_micros(self.max_micros)
+    }
+
+    /// Retrieve a percentile of the handling latency
+    ///
+    /// * `percentile` - The percentile to retrieve, in the range `[0.0, 100.0]`
+    ///
+    /// Returns the upper bound of the bucket holding the percentile (capped at
+    /// [LatencySnapshot::max]), or [Duration::ZERO] if nothing has been recorded
+    pub fn percentile(&self, percentile: f64) -> Duration {
+        if self.count == 0 {
+            return Duration::ZERO;
+        }
+        let percentile = percentile.clamp(0.0, 100.0);
+        let rank = ((percentile / 100.0) * self.count as f64).ceil().max(1.0) as u64;
+        let mut seen = 0u64;
+        for (index, count) in self.buckets.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return Duration::from_micros(bucket_upper_bound(index).min(self.max_micros));
+            }
+        }
+        self.max()
+    }
+
+    /// The median (50th percentile) handling latency
+    pub fn p50(&self) -> Duration {
+        self.percentile(50.0)
+    }
+
+    /// The 99th percentile handling latency
+    pub fn p99(&self) -> Duration {
+        self.percentile(99.0)
+    }
+
+    /// The 99.9th percentile handling latency
+    pub fn p999(&self) -> Duration {
+        self.percentile(99.9)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_index_bounds() {
+        for micros in 0..SUB_BUCKETS {
+            assert_eq!(micros as usize, bucket_index(micros));
+        }
+        // each bucket holds the latencies below its upper bound
+        for index in SUB_BUCKETS as usize..NUM_BUCKETS - 1 {
+            let upper = bucket_upper_bound(index);
+            assert_eq!(index, bucket_index(upper - 1));
+            assert_eq!(index + 1, bucket_index(upper));
+        }
+        assert_eq!(NUM_BUCKETS - 1, bucket_index((1 << 32) - 1));
+    }
+
+    #[test]
+    fn test_bucket_index_saturates() {
+        assert_eq!(NUM_BUCKETS - 1, bucket_index(1 << 32));
+        assert_eq!(NUM_BUCKETS - 1, bucket_index(1 << 33));
+        assert_eq!(NUM_BUCKETS - 1, bucket_index(u64::MAX));
+    }
+
+    #[test]
+    fn test_percentile_of_saturated_latency() {
+        let histogram = LatencyHistogram::new();
+        let latency = Duration::from_micros(1 << 33);
+        histogram.record(latency);
+        assert_eq!(latency, histogram.snapshot().p99());
+    }
+}
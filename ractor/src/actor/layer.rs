@@ -0,0 +1,313 @@
+// Copyright (c) Sean Lawlor
+//
+// This source code is licensed under both the MIT license found in the
+// LICENSE-MIT file in the root directory of this source tree.
+
+//! Middleware-style composition of message handlers
+//!
+//! A [HandlerLayer] wraps the `handle` logic of an [Actor] with reusable, cross-cutting
+//! behavior (logging, authorization, metrics, etc). Each layer receives the incoming message
+//! along with a [NextHandler] which invokes the next layer down the stack (and eventually the
+//! wrapped actor's own [Actor::handle]). A layer may inspect or transform the message before
+//! passing it on, act after the inner handler completes, or short-circuit by never calling
+//! `next` at all.
+//!
+//! Layers are stacked with [Layered], which is itself an [Actor] and can be spawned like
+//! any other. The most recently added layer is the outermost, and sees each message first.
+//!
+//! ```rust
+//! use ractor::{Actor, ActorProcessingErr, ActorRef, HandlerLayer, Layered, NextHandler};
+//!
+//! struct Business;
+//!
+//! #[cfg_attr(feature = "async-trait", ractor::async_trait)]
+//! impl Actor for Business {
+//!     type Msg = String;
+//!     type State = ();
+//!     type Arguments = ();
+//!
+//!     async fn pre_start(
+//!         &self,
+//!         _myself: ActorRef<Self::Msg>,
+//!         _: (),
+//!     ) -> Result<Self::State, ActorProcessingErr> {
+//!         Ok(())
+//!     }
+//! }
+//!
+//! struct Logging;
+//!
+//! #[cfg_attr(feature = "async-trait", ractor::async_trait)]
+//! impl<TActor: Actor> HandlerLayer<TActor> for Logging {
+//!     async fn handle(
+//!         &self,
+//!         myself: ActorRef<TActor::Msg>,
+//!         message: TActor::Msg,
+//!         state: &mut TActor::State,
+//!         next: NextHandler<'_, TActor>,
+//!     ) -> Result<(), ActorProcessingErr> {
+//!         tracing::info!("Actor {} received a message", myself.get_id());
+//!         next.run(myself, message, state).await
+//!     }
+//! }
+//!
+//! async fn example() {
+//!     let (actor, handle) = Actor::spawn(None, Layered::new(Logging, Business), ())
+//!         .await
+//!         .expect("Failed to spawn actor");
+//!     actor.cast("hello".to_string()).unwrap();
+//!     actor.stop(None);
+//!     handle.await.unwrap();
+//! }
+//! ```
+//!
+//! ## State
+//!
+//! Every layer operates on the wrapped actor's [Actor::State], passed down the stack by
+//! mutable reference, so a layer can read and update the same state the inner handler
+//! sees. Layers can't extend the state type; state belonging to the layer itself lives
+//! in the layer (behind interior mutability, since layers are shared by reference) or in
+//! the actor's locals (see [crate::ActorCell::set_local]).
+//!
+//! Only [Actor::handle] passes through the layers. The rest of the lifecycle (startup,
+//! supervision, shutdown, `handle_serialized` for remote messages) is delegated directly to
+//! the wrapped actor.
+//!
+//! ## Performance
+//!
+//! Stacks are composed statically. [Layered] is generic over both the layer and the actor it
+//! wraps, so a stack is a single concrete type and each call to `next` is statically
+//! dispatched, without any dynamic lookup of the next layer. With the `async-trait` feature
+//! enabled, every layer's future is boxed, costing one allocation per layer per message.
+
+#[cfg(not(feature = "async-trait"))]
+use std::future::Future;
+
+use crate::actor::messages::SupervisionEvent;
+use crate::{Actor, ActorProcessingErr, ActorRef};
+
+/// A reusable layer of message handling logic around an actor of type `TActor`. Implement
+/// it generically over `TActor` to share the layer across many actor types.
+///
+/// See the [module docs](self) for how layers are stacked.
+#[cfg_attr(feature = "async-trait", crate::async_trait)]
+pub trait HandlerLayer<TActor>: Sync + Send + 'static
+where
+    TActor: Actor,
+{
+    /// Handle an incoming message, optionally delegating to the rest of the stack with
+    /// [NextHandler::run]. Returning without calling `next` short-circuits the message, and
+    /// the inner layers and actor never see it.
+    ///
+    /// * `myself` - A handle to the [crate::ActorCell] representing this actor
+    /// * `message` - The message to process
+    /// * `state` - A mutable reference to the wrapped actor's state
+    /// * `next` - The remainder of the stack
+    #[cfg(not(feature = "async-trait"))]
+    fn handle(
+        &self,
+        myself: ActorRef<TActor::Msg>,
+        message: TActor::Msg,
+        state: &mut TActor::State,
+        next: NextHandler<'_, TActor>,
+    ) -> impl Future<Output = Result<(), ActorProcessingErr>> + Send;
+    /// Handle an incoming message, optionally delegating to the rest of the stack with
+    /// [NextHandler::run]. Returning without calling `next` short-circuits the message, and
+    /// the inner layers and actor never see it.
+    ///
+    /// * `myself` - A handle to the [crate::ActorCell] representing this actor
+    /// * `message` - The message to process
+    /// * `state` - A mutable reference to the wrapped actor's state
+    /// * `next` - The remainder of the stack
+    #[cfg(feature = "async-trait")]
+    async fn handle(
+        &self,
+        myself: ActorRef<TActor::Msg>,
+        message: TActor::Msg,
+        state: &mut TActor::State,
+        next: NextHandler<'_, TActor>,
+    ) -> Result<(), ActorProcessingErr>;
+}
+
+/// The remainder of a handler stack below a [HandlerLayer]
+pub struct NextHandler<'a, TActor> {
+    inner: &'a TActor,
+}
+
+impl<TActor> std::fmt::Debug for NextHandler<'_, TActor> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "NextHandler({})", std::any::type_name::<TActor>())
+    }
+}
+
+impl<TActor> NextHandler<'_, TActor>
+where
+    TActor: Actor,
+{
+    /// Pass the message on to the next layer in the stack (or the wrapped actor)
+    ///
+    /// * `myself` - A handle to the [crate::ActorCell] representing this actor
+    /// * `message` - The (possibly transformed) message to process
+    /// * `state` - A mutable reference to the wrapped actor's state
+    ///
+    /// Returns the result of the inner handler
+    pub async fn run(
+        self,
+        myself: ActorRef<TActor::Msg>,
+        message: TActor::Msg,
+        state: &mut TActor::State,
+    ) -> Result<(), ActorProcessingErr> {
+        self.inner.handle(myself, message, state).await
+    }
+}
+
+/// An [Actor] which runs each message through a [HandlerLayer] before the wrapped actor
+pub struct Layered<TLayer, TActor> {
+    layer: TLayer,
+    inner: TActor,
+}
+
+impl<TLayer, TActor> std::fmt::Debug for Layered<TLayer, TActor> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Layered({}, {})",
+            std::any::type_name::<TLayer>(),
+            std::any::type_name::<TActor>()
+        )
+    }
+}
+
+impl<TLayer, TActor> Layered<TLayer, TActor>
+where
+    TActor: Actor,
+    TLayer: HandlerLayer<TActor>,
+{
+    /// Wrap an actor with a layer
+    ///
+    /// * `layer` - The layer which sees messages before the actor
+    /// * `inner` - The wrapped actor
+    pub fn new(layer: TLayer, inner: TActor) -> Self {
+        Self { layer, inner }
+    }
+
+    /// Add another layer on top of this stack. The new layer becomes the outermost, and
+    /// sees each message before the layers already in the stack.
+    ///
+    /// * `layer` - The layer to add
+    ///
+    /// Returns the new stack
+    pub fn layer<TOuter>(self, layer: TOuter) -> Layered<TOuter, Self>
+    where
+        TOuter: HandlerLayer<Self>,
+    {
+        Layered::new(layer, self)
+    }
+
+    /// Retrieve the wrapped actor
+    pub fn inner(&self) -> &TActor {
+        &self.inner
+    }
+}
+
+#[cfg_attr(feature = "async-trait", crate::async_trait)]
+impl<TLayer, TActor> Actor for Layered<TLayer, TActor>
+where
+    TActor: Actor,
+    TLayer: HandlerLayer<TActor>,
+{
+    type Msg = TActor::Msg;
+    type State = TActor::State;
+    type Arguments = TActor::Arguments;
+
+    async fn pre_start(
+        &self,
+        myself: ActorRef<Self::Msg>,
+        args: Self::Arguments,
+    ) -> Result<Self::State, ActorProcessingErr> {
+        self.inner.pre_start(myself, args).await
+    }
+
+    async fn post_start(
+        &self,
+        myself: ActorRef<Self::Msg>,
+        state: &mut Self::State,
+    ) -> Result<(), ActorProcessingErr> {
+        self.inner.post_start(myself, state).await
+    }
+
+    async fn post_stop(
+        &self,
+        myself: ActorRef<Self::Msg>,
+        state: &mut Self::State,
+    ) -> Result<(), ActorProcessingErr> {
+        self.inner.post_stop(myself, state).await
+    }
+
+    async fn handle(
+        &self,
+        myself: ActorRef<Self::Msg>,
+        message: Self::Msg,
+        state: &mut Self::State,
+    ) -> Result<(), ActorProcessingErr> {
+        let next = NextHandler { inner: &self.inner };
+        self.layer.handle(myself, message, state, next).await
+    }
+
+    #[cfg(feature = "cluster")]
+    async fn handle_serialized(
+        &self,
+        myself: ActorRef<Self::Msg>,
+        message: crate::message::SerializedMessage,
+        state: &mut Self::State,
+    ) -> Result<(), ActorProcessingErr> {
+        self.inner.handle_serialized(myself, message, state).await
+    }
+
+    async fn handle_supervisor_evt(
+        &self,
+        myself: ActorRef<Self::Msg>,
+        message: SupervisionEvent,
+        state: &mut Self::State,
+    ) -> Result<(), ActorProcessingErr> {
+        self.inner
+            .handle_supervisor_evt(myself, message, state)
+            .await
+    }
+
+    fn supervision_batch_window(&self) -> Option<crate::concurrency::Duration> {
+        self.inner.supervision_batch_window()
+    }
+
+    fn message_weight(message: &Self::Msg) -> usize {
+        TActor::message_weight(message)
+    }
+
+    fn message_name(message: &Self::Msg) -> &'static str {
+        TActor::message_name(message)
+    }
+
+    fn max_total_mailbox_bytes(&self) -> Option<usize> {
+        self.inner.max_total_mailbox_bytes()
+    }
+
+    fn defer_readiness(&self) -> bool {
+        self.inner.defer_readiness()
+    }
+
+    #[cfg(feature = "latency_histogram")]
+    fn track_handling_latency(&self) -> bool {
+        self.inner.track_handling_latency()
+    }
+
+    async fn handle_supervisor_evt_batch(
+        &self,
+        myself: ActorRef<Self::Msg>,
+        messages: Vec<SupervisionEvent>,
+        state: &mut Self::State,
+    ) -> Result<(), ActorProcessingErr> {
+        self.inner
+            .handle_supervisor_evt_batch(myself, messages, state)
+            .await
+    }
+}
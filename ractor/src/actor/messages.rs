@@ -11,6 +11,7 @@
 
 use std::any::Any;
 use std::fmt::Debug;
+use std::sync::Arc;
 
 use crate::message::BoxedDowncastErr;
 use crate::ActorProcessingErr;
@@ -60,13 +61,83 @@ impl BoxedState {
     }
 }
 
+/// The reason an actor stopped. Well-known reasons can be matched on by supervisors
+/// and logs, while [StopReason::Custom] and [StopReason::Error] allow attaching
+/// arbitrary payloads.
+///
+/// An actor stopped without a reason (e.g. through `stop(None)`) reports no reason at all.
+/// The runtime sets [StopReason::Killed], [StopReason::Drained], and [StopReason::Shutdown]
+/// (for [crate::time::exit_after]), the other reasons are provided by whoever stops the actor.
+///
+/// [From] is implemented for [String] and [&str], both of which map to
+/// [StopReason::Custom], so string reasons can be provided with `.into()`. Errors
+/// ([ActorProcessingErr]) convert to [StopReason::Error].
+///
+/// Further well-known reasons may be added, so matches on a [StopReason] need a
+/// fallback arm.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum StopReason {
+    /// The actor finished its work and stopped normally, e.g. through
+    /// `myself.stop_with_reason(Some(StopReason::Normal))`
+    Normal,
+    /// The actor was stopped as part of a (possibly timed) shutdown, with optional context
+    Shutdown(Option<String>),
+    /// The actor was stopped by its supervisor (see
+    /// [crate::ActorCell::stop_children_with_reason])
+    Supervisor,
+    /// The actor was killed (see [Signal::Kill]). Set by the runtime
+    Killed,
+    /// The actor drained its message queue and stopped (see [crate::ActorCell::drain]).
+    /// Set by the runtime
+    Drained,
+    /// A custom, free-form, reason
+    Custom(String),
+    /// The actor stopped due to an error. The error is shared so the reason can be
+    /// cloned, e.g. when stopping many children with the same reason
+    Error(Arc<dyn std::error::Error + Send + Sync + 'static>),
+}
+
+impl std::fmt::Display for StopReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Normal => write!(f, "normal"),
+            Self::Shutdown(None) => write!(f, "shutdown"),
+            Self::Shutdown(Some(context)) => write!(f, "shutdown ({context})"),
+            Self::Supervisor => write!(f, "supervisor"),
+            Self::Killed => write!(f, "{}", Signal::Kill),
+            Self::Drained => write!(f, "Drained"),
+            Self::Custom(reason) => write!(f, "{reason}"),
+            Self::Error(err) => write!(f, "error: {err}"),
+        }
+    }
+}
+
+impl From<String> for StopReason {
+    fn from(value: String) -> Self {
+        Self::Custom(value)
+    }
+}
+
+impl From<&str> for StopReason {
+    fn from(value: &str) -> Self {
+        Self::Custom(value.to_string())
+    }
+}
+
+impl From<ActorProcessingErr> for StopReason {
+    fn from(value: ActorProcessingErr) -> Self {
+        Self::Error(Arc::from(value))
+    }
+}
+
 /// Messages to stop an actor
 #[derive(Debug)]
 pub enum StopMessage {
     /// Normal stop
     Stop,
     /// Stop with a reason
-    Reason(String),
+    Reason(StopReason),
 }
 
 impl std::fmt::Display for StopMessage {
@@ -89,7 +160,7 @@ pub enum SupervisionEvent {
     ActorTerminated(
         super::actor_cell::ActorCell,
         Option<BoxedState>,
-        Option<String>,
+        Option<StopReason>,
     ),
     /// An actor failed (due to panic or error case)
     ActorFailed(super::actor_cell::ActorCell, ActorProcessingErr),
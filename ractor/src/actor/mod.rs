@@ -66,10 +66,14 @@ pub mod messages;
 use messages::*;
 
 pub mod actor_cell;
+pub mod actor_count;
 pub mod actor_id;
 pub(crate) mod actor_properties;
 pub mod actor_ref;
-mod supervision;
+#[cfg(feature = "latency_histogram")]
+pub mod latency;
+pub mod layer;
+pub mod supervision;
 
 #[cfg(test)]
 mod tests;
@@ -103,6 +107,7 @@ pub(crate) fn get_panic_string(e: Box<dyn std::any::Any + Send>) -> ActorProcess
 /// * `handle`
 /// * `handle_serialized` (Available with `cluster` feature only)
 /// * `handle_supervisor_evt`
+/// * `handle_supervisor_evt_batch`
 ///
 /// return a [Result<_, ActorProcessingError>] where the error type is an
 /// alias of [Box<dyn std::error::Error + Send + Sync + 'static>]. This is treated
@@ -351,6 +356,116 @@ pub trait Actor: Sized + Sync + Send + 'static {
         Ok(())
     }
 
+    /// Opt into batched delivery of supervision events. When this returns [Some(Duration)],
+    /// supervision events which arrive within that window of the first event are collected
+    /// and delivered together to [Actor::handle_supervisor_evt_batch], allowing the supervisor
+    /// to make a consolidated decision (e.g. during a crash storm of many children).
+    ///
+    /// A stop request closes the window early: the events collected so far are handled
+    /// before the actor stops. A kill signal interrupts the collection immediately, dropping
+    /// the batch.
+    ///
+    /// The default is [None], delivering each event individually to [Actor::handle_supervisor_evt]
+    fn supervision_batch_window(&self) -> Option<crate::concurrency::Duration> {
+        None
+    }
+
+    /// The weight of a message, (e.g. the approximate number of bytes it holds) which
+    /// counts towards the mailbox's byte budget (see [Actor::max_total_mailbox_bytes]).
+    /// It's computed by the sender when the message is sent, so it should be cheap and
+    /// deterministic. It's only computed for actors which have a budget.
+    ///
+    /// The default is the shallow size of the message, heap allocations aren't counted
+    fn message_weight(message: &Self::Msg) -> usize {
+        std::mem::size_of_val(message)
+    }
+
+    /// The name of a message reported while it's being handled (see
+    /// [ActorCell::current_message]), e.g. the name of its variant. It's computed for every
+    /// handled message, so it should be cheap.
+    ///
+    /// The default is the type name of [Actor::Msg], which is the same for every message
+    #[allow(unused_variables)]
+    fn message_name(message: &Self::Msg) -> &'static str {
+        std::any::type_name::<Self::Msg>()
+    }
+
+    /// The byte budget of this actor's mailbox, read once at spawn. Sends which would push
+    /// the total weight (see [Actor::message_weight]) of the queued messages over the budget
+    /// are rejected with [MessagingErr::MailboxBudgetExceeded] before being enqueued, bounding
+    /// the memory held by the mailbox even when message sizes vary wildly.
+    ///
+    /// The default is [None], an unbounded mailbox
+    fn max_total_mailbox_bytes(&self) -> Option<usize> {
+        None
+    }
+
+    /// Opt into deferred readiness, read once at spawn. By default an actor is ready
+    /// (see [ActorCell::is_ready]) as soon as it's running. An actor which needs further
+    /// warmup before serving traffic can defer its readiness until it calls
+    /// [ActorCell::mark_ready] on its own `myself` handle. Messages sent in the meantime
+    /// still queue in the mailbox.
+    ///
+    /// The default is [false], the actor is ready once it's running
+    fn defer_readiness(&self) -> bool {
+        false
+    }
+
+    /// Opt this actor into recording the latency of every handled message into a histogram,
+    /// read once at spawn. The summary is available from [ActorCell::latency_snapshot]. See
+    /// the [latency] module for the bucket configuration and memory cost.
+    ///
+    /// The default is [false], no latencies are recorded
+    #[cfg(feature = "latency_histogram")]
+    fn track_handling_latency(&self) -> bool {
+        false
+    }
+
+    /// Handle a batch of supervision events, collected when [Actor::supervision_batch_window]
+    /// is set. Unhandled panics will be captured and sent the the supervisor(s). The default
+    /// implementation passes each event in order to [Actor::handle_supervisor_evt].
+    ///
+    /// * `myself` - A handle to the [ActorCell] representing this actor
+    /// * `messages` - The supervision events received within the batching window, in arrival order
+    /// * `state` - A mutable reference to the internal actor's state
+    #[allow(unused_variables)]
+    #[cfg(not(feature = "async-trait"))]
+    fn handle_supervisor_evt_batch(
+        &self,
+        myself: ActorRef<Self::Msg>,
+        messages: Vec<SupervisionEvent>,
+        state: &mut Self::State,
+    ) -> impl Future<Output = Result<(), ActorProcessingErr>> + Send {
+        async move {
+            for message in messages {
+                self.handle_supervisor_evt(myself.clone(), message, state)
+                    .await?;
+            }
+            Ok(())
+        }
+    }
+    /// Handle a batch of supervision events, collected when [Actor::supervision_batch_window]
+    /// is set. Unhandled panics will be captured and sent the the supervisor(s). The default
+    /// implementation passes each event in order to [Actor::handle_supervisor_evt].
+    ///
+    /// * `myself` - A handle to the [ActorCell] representing this actor
+    /// * `messages` - The supervision events received within the batching window, in arrival order
+    /// * `state` - A mutable reference to the internal actor's state
+    #[allow(unused_variables)]
+    #[cfg(feature = "async-trait")]
+    async fn handle_supervisor_evt_batch(
+        &self,
+        myself: ActorRef<Self::Msg>,
+        messages: Vec<SupervisionEvent>,
+        state: &mut Self::State,
+    ) -> Result<(), ActorProcessingErr> {
+        for message in messages {
+            self.handle_supervisor_evt(myself.clone(), message, state)
+                .await?;
+        }
+        Ok(())
+    }
+
     /// Spawn an actor of this type, which is unsupervised, automatically starting
     ///
     /// * `name`: A name to give the actor. Useful for global referencing or debug printing
@@ -434,7 +549,7 @@ pub trait Actor: Sized + Sync + Send + 'static {
 #[doc(hidden)]
 struct ActorLoopResult {
     should_exit: bool,
-    exit_reason: Option<String>,
+    exit_reason: Option<StopReason>,
     was_killed: bool,
 }
 
@@ -447,7 +562,7 @@ impl ActorLoopResult {
         }
     }
 
-    pub(crate) fn stop(reason: Option<String>) -> Self {
+    pub(crate) fn stop(reason: Option<StopReason>) -> Self {
         Self {
             should_exit: true,
             exit_reason: reason,
@@ -455,10 +570,10 @@ impl ActorLoopResult {
         }
     }
 
-    pub(crate) fn signal(signal_str: Option<String>) -> Self {
+    pub(crate) fn signal(reason: Option<StopReason>) -> Self {
         Self {
             should_exit: true,
-            exit_reason: signal_str,
+            exit_reason: reason,
             was_killed: true,
         }
     }
@@ -542,6 +657,34 @@ where
         result
     }
 
+    /// Spawn the replacement of an actor, optionally with a supervisor. The replacement
+    /// carries the name but doesn't register it, so it can be handed over once the
+    /// replacement has started (see [ActorCell::hot_restart])
+    ///
+    /// * `name`: The name of the actor being replaced
+    /// * `handler` The [Actor] defining the logic for this actor
+    /// * `startup_args`: Arguments passed to the `pre_start` call of the [Actor] to facilitate startup and
+    ///   initial state creation
+    /// * `supervisor`: The [ActorCell] which is to become the supervisor (parent) of this actor, if any
+    ///
+    /// Returns a [Ok((ActorRef, JoinHandle<()>))] upon successful start, denoting the actor reference
+    /// along with the join handle which will complete when the actor terminates. Returns [Err(SpawnErr)] if
+    /// the actor failed to start
+    pub(crate) async fn spawn_replacement(
+        name: Option<ActorName>,
+        handler: TActor,
+        startup_args: TActor::Arguments,
+        supervisor: Option<ActorCell>,
+    ) -> Result<(ActorRef<TActor::Msg>, JoinHandle<()>), SpawnErr> {
+        let (actor, ports) = Self::new_with_registration(name, handler, false)?;
+        let aref = actor.actor_ref.clone();
+        let result = actor.start(ports, startup_args, supervisor).await;
+        if result.is_err() {
+            aref.set_status(ActorStatus::Stopped);
+        }
+        result
+    }
+
     /// Spawn an actor instantly, not waiting on the actor's `pre_start` routine. This is helpful
     /// for actors where you want access to the send messages into the actor's message queue
     /// without waiting on an asynchronous context.
@@ -684,7 +827,31 @@ where
     ///
     /// Returns A tuple [(Actor, ActorPortSet)] to be passed to the `start` function of [Actor]
     fn new(name: Option<ActorName>, handler: TActor) -> Result<(Self, ActorPortSet), SpawnErr> {
-        let (actor_cell, ports) = actor_cell::ActorCell::new::<TActor>(name)?;
+        Self::new_with_registration(name, handler, true)
+    }
+
+    /// Create a new actor like [ActorRuntime::new], optionally leaving the name out of the
+    /// registry
+    ///
+    /// * `name`: A name to give the actor. Useful for global referencing or debug printing
+    /// * `handler` The [Actor] defining the logic for this actor
+    /// * `register_name`: Whether to register the name in the registry
+    ///
+    /// Returns A tuple [(Actor, ActorPortSet)] to be passed to the `start` function of [Actor]
+    fn new_with_registration(
+        name: Option<ActorName>,
+        handler: TActor,
+        register_name: bool,
+    ) -> Result<(Self, ActorPortSet), SpawnErr> {
+        let (actor_cell, ports) = actor_cell::ActorCell::new::<TActor>(name, register_name)?;
+        actor_cell.set_mailbox_byte_budget(handler.max_total_mailbox_bytes());
+        if handler.defer_readiness() {
+            actor_cell.set_ready(false);
+        }
+        #[cfg(feature = "latency_histogram")]
+        if handler.track_handling_latency() {
+            actor_cell.enable_latency_tracking();
+        }
         let id = actor_cell.get_id();
         let name = actor_cell.get_name();
         Ok((
@@ -759,7 +926,7 @@ where
                     ActorErr::Cancelled => SupervisionEvent::ActorTerminated(
                         myself.get_cell(),
                         None,
-                        Some("killed".to_string()),
+                        Some(StopReason::Killed),
                     ),
                     ActorErr::Failed(msg) => SupervisionEvent::ActorFailed(myself.get_cell(), msg),
                 },
@@ -791,7 +958,7 @@ where
         myself: ActorRef<TActor::Msg>,
         _id: ActorId,
         _name: Option<String>,
-    ) -> Result<Option<String>, ActorErr> {
+    ) -> Result<Option<StopReason>, ActorErr> {
         // perform the post-start, with supervision enabled
         Self::do_post_start(myself.clone(), handler, state)
             .await?
@@ -862,30 +1029,36 @@ where
                     Ok(ActorLoopResult::signal(Self::handle_signal(myself, signal)))
                 }
                 actor_cell::ActorPortMessage::Stop(stop_message) => {
-                    let exit_reason = match stop_message {
-                        StopMessage::Stop => {
-                            tracing::trace!("Actor {:?} stopped with no reason", myself.get_id());
-                            None
-                        }
-                        StopMessage::Reason(reason) => {
-                            tracing::trace!(
-                                "Actor {:?} stopped with reason '{reason}'",
-                                myself.get_id(),
-                            );
-                            Some(reason)
-                        }
-                    };
-                    Ok(ActorLoopResult::stop(exit_reason))
+                    Ok(Self::handle_stop(myself, stop_message))
                 }
                 actor_cell::ActorPortMessage::Supervision(supervision) => {
-                    let future = Self::handle_supervision_message(
-                        myself.clone(),
-                        state,
-                        handler,
-                        supervision,
-                    );
-                    match ports.run_with_signal(future).await {
-                        Ok(Ok(())) => Ok(ActorLoopResult::ok()),
+                    let mut stop_message = None;
+                    let result = if let Some(window) = handler.supervision_batch_window() {
+                        match ports.collect_supervision_batch(supervision, window).await {
+                            Ok((batch, stop)) => {
+                                stop_message = stop;
+                                let future = handler.handle_supervisor_evt_batch(
+                                    myself.clone(),
+                                    batch,
+                                    state,
+                                );
+                                ports.run_with_signal(future).await
+                            }
+                            Err(signal) => Err(signal),
+                        }
+                    } else {
+                        let future = Self::handle_supervision_message(
+                            myself.clone(),
+                            state,
+                            handler,
+                            supervision,
+                        );
+                        ports.run_with_signal(future).await
+                    };
+                    match result {
+                        Ok(Ok(())) => Ok(stop_message
+                            .map(|stop_message| Self::handle_stop(myself, stop_message))
+                            .unwrap_or_else(ActorLoopResult::ok)),
                         Ok(Err(internal_err)) => Err(internal_err),
                         Err(signal) => {
                             Ok(ActorLoopResult::signal(Self::handle_signal(myself, signal)))
@@ -893,8 +1066,18 @@ where
                     }
                 }
                 actor_cell::ActorPortMessage::Message(MuxedMessage::Message(msg)) => {
+                    #[cfg(feature = "latency_histogram")]
+                    let started = crate::concurrency::Instant::now();
                     let future = Self::handle_message(myself.clone(), state, handler, msg);
-                    match ports.run_with_signal(future).await {
+                    let result = ports.run_with_signal(future).await;
+                    // the message is no longer in-flight, regardless of how handling finished
+                    myself.set_current_message(None);
+                    #[cfg(feature = "latency_histogram")]
+                    if result.is_ok() {
+                        // only handlers which ran to completion are recorded, not those interrupted by a signal
+                        myself.record_handling_latency(started.elapsed());
+                    }
+                    match result {
                         Ok(Ok(())) => Ok(ActorLoopResult::ok()),
                         Ok(Err(internal_err)) => Err(internal_err),
                         Err(signal) => {
@@ -905,7 +1088,7 @@ where
                 actor_cell::ActorPortMessage::Message(MuxedMessage::Drain) => {
                     // Drain is a stub marker that the actor should now stop, we've processed
                     // all the messages and we want the actor to die now
-                    Ok(ActorLoopResult::stop(Some("Drained".to_string())))
+                    Ok(ActorLoopResult::stop(Some(StopReason::Drained)))
                 }
             },
             Err(MessagingErr::ChannelClosed) => {
@@ -918,14 +1101,14 @@ where
                     Signal::Kill,
                 )))
             }
-            Err(MessagingErr::InvalidActorType) => {
+            Err(MessagingErr::InvalidActorType | MessagingErr::RedirectCycle) => {
                 // not possible. Treat like a channel closed
                 Ok(ActorLoopResult::signal(Self::handle_signal(
                     myself,
                     Signal::Kill,
                 )))
             }
-            Err(MessagingErr::SendErr(_)) => {
+            Err(MessagingErr::SendErr(_) | MessagingErr::MailboxBudgetExceeded(_)) => {
                 // not possible. Treat like a channel closed
                 Ok(ActorLoopResult::signal(Self::handle_signal(
                     myself,
@@ -968,9 +1151,16 @@ where
         // to automatically continue tracing span nesting when sending messages to Actors.
         let current_span_when_message_was_sent = msg.span.take();
 
+        // the message has left the mailbox, release whatever weight it reserved in the
+        // byte budget. Serialized messages and mailboxes without a budget reserve nothing
+        myself.release_mailbox_bytes(msg.weight);
+
         // An error here will bubble up to terminate the actor
         let typed_msg = TActor::Msg::from_boxed(msg)?;
 
+        // track the in-flight message for introspection
+        myself.set_current_message(Some(TActor::message_name(&typed_msg)));
+
         if let Some(span) = current_span_when_message_was_sent {
             handler
                 .handle(myself, typed_msg, state)
@@ -981,13 +1171,27 @@ where
         }
     }
 
-    fn handle_signal(myself: ActorRef<TActor::Msg>, signal: Signal) -> Option<String> {
+    fn handle_stop(myself: ActorRef<TActor::Msg>, stop_message: StopMessage) -> ActorLoopResult {
+        let exit_reason = match stop_message {
+            StopMessage::Stop => {
+                tracing::trace!("Actor {:?} stopped with no reason", myself.get_id());
+                None
+            }
+            StopMessage::Reason(reason) => {
+                tracing::trace!("Actor {:?} stopped with reason '{reason}'", myself.get_id());
+                Some(reason)
+            }
+        };
+        ActorLoopResult::stop(exit_reason)
+    }
+
+    fn handle_signal(myself: ActorRef<TActor::Msg>, signal: Signal) -> Option<StopReason> {
         match &signal {
             Signal::Kill => {
                 myself.terminate();
             }
         }
-        Some(signal.to_string())
+        Some(StopReason::Killed)
     }
 
     async fn handle_supervision_message(
@@ -13,12 +13,150 @@
 //!
 //! This is currently an initial implementation of [Erlang supervisors](https://www.erlang.org/doc/man/supervisor.html)
 //! which will be expanded upon as the library develops.
+//!
+//! ## Restart decisions
+//!
+//! A supervisor can delegate the choice of what to do with an exited child to a [RestartPolicy].
+//! The policy is handed a [ChildExitReason], which carries the child's stop reason or its
+//! failure (an [ActorProcessingErr] which can be downcast to the concrete error type), and
+//! returns a [RestartDecision]. The [DefaultRestartPolicy] restarts children which exited
+//! abnormally (failed, were killed, or were stopped with [StopReason::Error]) and leaves
+//! children which stopped normally stopped.
+//!
+//! A custom decision function is any `Fn(&ChildExitReason<'_>) -> RestartDecision`, for example
+//! restarting on transient errors while escalating on fatal ones:
+//!
+//! ```rust
+//! use ractor::actor::supervision::{ChildExitReason, RestartDecision, RestartPolicy};
+//!
+//! #[derive(Debug)]
+//! struct ConfigInvalid;
+//! impl std::fmt::Display for ConfigInvalid {
+//!     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+//!         write!(f, "config invalid")
+//!     }
+//! }
+//! impl std::error::Error for ConfigInvalid {}
+//!
+//! let policy = |reason: &ChildExitReason<'_>| match reason {
+//!     ChildExitReason::Failed(err) if err.is::<ConfigInvalid>() => RestartDecision::Escalate,
+//!     ChildExitReason::Failed(_) | ChildExitReason::Killed => RestartDecision::Restart,
+//!     ChildExitReason::Stopped(_) => RestartDecision::Stop,
+//! };
+//!
+//! let fatal: ractor::ActorProcessingErr = Box::new(ConfigInvalid);
+//! assert_eq!(
+//!     RestartDecision::Escalate,
+//!     policy.decide(&ChildExitReason::Failed(&fatal))
+//! );
+//! ```
+//!
+//! The supervisor then acts on the decision in its `handle_supervisor_evt`, typically by
+//! re-spawning the child for [RestartDecision::Restart] and returning an error for
+//! [RestartDecision::Escalate], which fails the supervisor and notifies its own supervisor.
 
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
-use super::{actor_cell::ActorCell, messages::SupervisionEvent};
-use crate::ActorId;
+use super::{
+    actor_cell::ActorCell,
+    messages::{StopReason, SupervisionEvent},
+};
+use crate::{ActorId, ActorProcessingErr};
+
+/// What a supervisor should do with a child which has exited
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum RestartDecision {
+    /// Start a new instance of the child
+    Restart,
+    /// Leave the child stopped
+    Stop,
+    /// Leave the child stopped and fail the supervisor, escalating the failure to
+    /// the supervisor's own supervisor
+    Escalate,
+}
+
+/// The reason a supervised child exited, as seen by a [RestartPolicy]
+#[derive(Debug)]
+pub enum ChildExitReason<'a> {
+    /// The child stopped, with the stop reason if one was provided
+    Stopped(Option<&'a StopReason>),
+    /// The child was killed (see [crate::Signal::Kill])
+    Killed,
+    /// The child failed, either panicking or returning an error. The error can be
+    /// downcast to inspect the concrete failure type
+    Failed(&'a ActorProcessingErr),
+}
+
+impl<'a> ChildExitReason<'a> {
+    /// Extract the exit reason from a [SupervisionEvent]
+    ///
+    /// Returns [Some(ChildExitReason)] if the event denotes an actor exiting, [None] otherwise
+    pub fn from_event(event: &'a SupervisionEvent) -> Option<Self> {
+        match event {
+            SupervisionEvent::ActorTerminated(_, _, reason) => match reason {
+                Some(StopReason::Killed) => Some(Self::Killed),
+                r => Some(Self::Stopped(r.as_ref())),
+            },
+            SupervisionEvent::ActorFailed(_, err) => Some(Self::Failed(err)),
+            _ => None,
+        }
+    }
+
+    /// Identifies if the child exited abnormally
+    ///
+    /// Returns [true] if the child failed, was killed, or was stopped with
+    /// [StopReason::Error], [false] if it stopped gracefully
+    pub fn is_abnormal(&self) -> bool {
+        !matches!(self, Self::Stopped(reason) if !matches!(reason, Some(StopReason::Error(_))))
+    }
+}
+
+/// A policy deciding whether a supervised child should be restarted once it exits.
+///
+/// This is implemented for any `Fn(&ChildExitReason<'_>) -> RestartDecision` so a closure
+/// can be used directly as a policy.
+pub trait RestartPolicy: Send + Sync + 'static {
+    /// Decide what to do with a child which exited
+    ///
+    /// * `reason` - The [ChildExitReason] describing why the child exited
+    ///
+    /// Returns the [RestartDecision] for the child
+    fn decide(&self, reason: &ChildExitReason<'_>) -> RestartDecision;
+
+    /// Decide what to do with the child referred to by a [SupervisionEvent]
+    ///
+    /// * `event` - The [SupervisionEvent] received by the supervisor
+    ///
+    /// Returns [Some(RestartDecision)] if the event denotes a child exiting, [None] otherwise
+    fn decide_event(&self, event: &SupervisionEvent) -> Option<RestartDecision> {
+        ChildExitReason::from_event(event).map(|reason| self.decide(&reason))
+    }
+}
+
+impl<F> RestartPolicy for F
+where
+    F: Fn(&ChildExitReason<'_>) -> RestartDecision + Send + Sync + 'static,
+{
+    fn decide(&self, reason: &ChildExitReason<'_>) -> RestartDecision {
+        self(reason)
+    }
+}
+
+/// The default [RestartPolicy]: restart children which exited abnormally (see
+/// [ChildExitReason::is_abnormal]), and leave children which stopped normally stopped
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DefaultRestartPolicy;
+
+impl RestartPolicy for DefaultRestartPolicy {
+    fn decide(&self, reason: &ChildExitReason<'_>) -> RestartDecision {
+        if reason.is_abnormal() {
+            RestartDecision::Restart
+        } else {
+            RestartDecision::Stop
+        }
+    }
+}
 
 /// A supervision tree
 #[derive(Default, Debug)]
@@ -60,10 +198,10 @@ impl SupervisionTree {
     }
 
     /// Stop all the linked children, but does NOT unlink them (stop flow will do that)
-    pub(crate) fn stop_all_children(&self, reason: Option<String>) {
+    pub(crate) fn stop_all_children(&self, reason: Option<StopReason>) {
         let cells = self.get_children();
         for cell in cells {
-            cell.stop(reason.clone());
+            cell.stop_with_reason(reason.clone());
         }
     }
 
@@ -79,7 +217,7 @@ impl SupervisionTree {
     /// and wait for them to exit (concurrently)
     pub(crate) async fn stop_all_children_and_wait(
         &self,
-        reason: Option<String>,
+        reason: Option<StopReason>,
         timeout: Option<crate::concurrency::Duration>,
     ) {
         let cells = self.get_children();
@@ -87,7 +225,7 @@ impl SupervisionTree {
         for cell in cells {
             let lreason = reason.clone();
             let ltimeout = timeout;
-            js.spawn(async move { cell.stop_and_wait(lreason, ltimeout).await });
+            js.spawn(async move { cell.stop_and_wait_with_reason(lreason, ltimeout).await });
         }
         // drain the tasks
         while let Some(res) = js.join_next().await {
@@ -120,6 +258,28 @@ impl SupervisionTree {
         }
     }
 
+    /// Move all the children to another supervisor. They're removed from this tree
+    /// before being linked to `supervisor` so they aren't terminated along with
+    /// this actor when it exits
+    pub(crate) fn transfer_children(&self, supervisor: &ActorCell) {
+        let cells = self
+            .children
+            .lock()
+            .unwrap()
+            .drain()
+            .map(|(_, cell)| cell)
+            .collect::<Vec<_>>();
+        // the guard is dropped above to not deadlock on double-link
+        for cell in cells {
+            cell.link(supervisor.clone());
+        }
+    }
+
+    /// Retrieve the supervisor, if any
+    pub(crate) fn get_supervisor(&self) -> Option<ActorCell> {
+        self.supervisor.lock().unwrap().clone()
+    }
+
     /// Determine if the specified actor is a parent of this actor
     pub(crate) fn is_child_of(&self, id: ActorId) -> bool {
         if let Some(parent) = &*(self.supervisor.lock().unwrap()) {
@@ -0,0 +1,130 @@
+// Copyright (c) Sean Lawlor
+//
+// This source code is licensed under both the MIT license found in the
+// LICENSE-MIT file in the root directory of this source tree.
+
+//! Handler layer tests
+
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+use crate::{common_test::periodic_check, concurrency::Duration};
+
+use crate::{Actor, ActorProcessingErr, ActorRef, HandlerLayer, Layered, NextHandler};
+
+#[crate::concurrency::test]
+#[tracing_test::traced_test]
+async fn test_layers_transform_and_short_circuit_messages() {
+    struct Summer;
+
+    #[cfg_attr(feature = "async-trait", crate::async_trait)]
+    impl Actor for Summer {
+        type Msg = u64;
+        type State = Arc<AtomicU64>;
+        type Arguments = Arc<AtomicU64>;
+
+        async fn pre_start(
+            &self,
+            _this_actor: crate::ActorRef<Self::Msg>,
+            sum: Arc<AtomicU64>,
+        ) -> Result<Self::State, ActorProcessingErr> {
+            Ok(sum)
+        }
+
+        async fn handle(
+            &self,
+            _: ActorRef<Self::Msg>,
+            message: Self::Msg,
+            sum: &mut Self::State,
+        ) -> Result<(), ActorProcessingErr> {
+            sum.fetch_add(message, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    /// Counts every message reaching this layer
+    struct Counter(Arc<AtomicU64>);
+
+    #[cfg_attr(feature = "async-trait", crate::async_trait)]
+    impl<TActor: Actor> HandlerLayer<TActor> for Counter {
+        async fn handle(
+            &self,
+            myself: ActorRef<TActor::Msg>,
+            message: TActor::Msg,
+            state: &mut TActor::State,
+            next: NextHandler<'_, TActor>,
+        ) -> Result<(), ActorProcessingErr> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            next.run(myself, message, state).await
+        }
+    }
+
+    /// Drops any message over 100
+    struct Reject;
+
+    #[cfg_attr(feature = "async-trait", crate::async_trait)]
+    impl<TActor: Actor<Msg = u64>> HandlerLayer<TActor> for Reject {
+        async fn handle(
+            &self,
+            myself: ActorRef<TActor::Msg>,
+            message: TActor::Msg,
+            state: &mut TActor::State,
+            next: NextHandler<'_, TActor>,
+        ) -> Result<(), ActorProcessingErr> {
+            if message > 100 {
+                return Ok(());
+            }
+            next.run(myself, message, state).await
+        }
+    }
+
+    /// Doubles every message
+    struct Double;
+
+    #[cfg_attr(feature = "async-trait", crate::async_trait)]
+    impl<TActor: Actor<Msg = u64>> HandlerLayer<TActor> for Double {
+        async fn handle(
+            &self,
+            myself: ActorRef<TActor::Msg>,
+            message: TActor::Msg,
+            state: &mut TActor::State,
+            next: NextHandler<'_, TActor>,
+        ) -> Result<(), ActorProcessingErr> {
+            next.run(myself, message * 2, state).await
+        }
+    }
+
+    let seen = Arc::new(AtomicU64::new(0));
+    let sum = Arc::new(AtomicU64::new(0));
+
+    // the outermost layer, Counter, sees every message before Reject drops the large one
+    let stack = Layered::new(Double, Summer)
+        .layer(Reject)
+        .layer(Counter(seen.clone()));
+    let (actor, handle) = Actor::spawn(None, stack, sum.clone())
+        .await
+        .expect("Failed to start test actor");
+
+    for message in [1, 200, 3] {
+        actor
+            .cast(message)
+            .expect("Failed to send message to actor");
+    }
+
+    periodic_check(
+        || seen.load(Ordering::SeqCst) == 3,
+        Duration::from_millis(500),
+    )
+    .await;
+    periodic_check(
+        || sum.load(Ordering::SeqCst) == 8,
+        Duration::from_millis(500),
+    )
+    .await;
+
+    // cleanup
+    actor.stop(None);
+    handle.await.unwrap();
+}
@@ -20,6 +20,7 @@ use crate::{
     Actor, ActorCell, ActorProcessingErr, ActorRef, ActorStatus, SpawnErr, SupervisionEvent,
 };
 
+mod layer;
 mod supervisor;
 
 struct EmptyMessage;
@@ -475,6 +476,119 @@ async fn test_serialized_cast() {
     handle.await.unwrap();
 }
 
+#[cfg(feature = "cluster")]
+#[crate::concurrency::test]
+#[tracing_test::traced_test]
+async fn serialized_messages_dont_release_unreserved_mailbox_bytes() {
+    use crate::message::{BoxedDowncastErr, SerializedMessage};
+    use crate::Message;
+
+    struct TestActor {
+        counter: Arc<AtomicU8>,
+        gate: Arc<crate::concurrency::Notify>,
+    }
+    struct TestMessage;
+    impl Message for TestMessage {
+        fn serializable() -> bool {
+            true
+        }
+        fn deserialize(_bytes: SerializedMessage) -> Result<Self, BoxedDowncastErr> {
+            Ok(TestMessage)
+        }
+        fn serialize(self) -> Result<SerializedMessage, BoxedDowncastErr> {
+            Ok(crate::message::SerializedMessage::Cast {
+                variant: "Cast".to_string(),
+                args: vec![],
+                metadata: None,
+            })
+        }
+    }
+
+    #[cfg_attr(feature = "async-trait", crate::async_trait)]
+    impl Actor for TestActor {
+        type Msg = TestMessage;
+        type State = ();
+        type Arguments = ();
+        async fn pre_start(
+            &self,
+            _myself: ActorRef<Self::Msg>,
+            _: (),
+        ) -> Result<Self::State, ActorProcessingErr> {
+            Ok(())
+        }
+
+        async fn handle(
+            &self,
+            _myself: ActorRef<Self::Msg>,
+            _message: TestMessage,
+            _state: &mut Self::State,
+        ) -> Result<(), ActorProcessingErr> {
+            self.gate.notified().await;
+            self.counter.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+
+        fn message_weight(_: &Self::Msg) -> usize {
+            10
+        }
+
+        fn max_total_mailbox_bytes(&self) -> Option<usize> {
+            Some(100)
+        }
+    }
+
+    let counter = Arc::new(AtomicU8::new(0));
+    let gate = Arc::new(crate::concurrency::Notify::new());
+    let (actor, handle) = Actor::spawn(
+        None,
+        TestActor {
+            counter: counter.clone(),
+            gate: gate.clone(),
+        },
+        (),
+    )
+    .await
+    .expect("Failed to spawn test actor");
+
+    // the first message is picked up and held in the handler
+    actor
+        .cast(TestMessage)
+        .expect("Failed to send message to actor");
+    periodic_check(|| actor.mailbox_bytes() == 0, Duration::from_millis(500)).await;
+
+    // serialized messages bypass the budget, so only the regular message is weighed
+    actor
+        .send_serialized(TestMessage.serialize().unwrap())
+        .expect("Serialized message send failed!");
+    actor
+        .cast(TestMessage)
+        .expect("Failed to send message to actor");
+    assert_eq!(10, actor.mailbox_bytes());
+
+    // dequeuing the serialized message leaves the regular message's weight in place
+    gate.notify_one();
+    periodic_check(
+        || counter.load(Ordering::SeqCst) == 1,
+        Duration::from_millis(500),
+    )
+    .await;
+    sleep(Duration::from_millis(50)).await;
+    assert_eq!(10, actor.mailbox_bytes());
+
+    gate.notify_one();
+    gate.notify_one();
+    periodic_check(
+        || counter.load(Ordering::SeqCst) == 3,
+        Duration::from_millis(500),
+    )
+    .await;
+    assert_eq!(0, actor.mailbox_bytes());
+
+    // cleanup
+    actor.stop(None);
+    handle.await.unwrap();
+}
+
 #[cfg(feature = "cluster")]
 fn port_forward<Tin, Tout, F>(
     typed_port: crate::RpcReplyPort<Tout>,
@@ -915,7 +1029,7 @@ fn test_err_map() {
 #[test]
 fn returns_actor_references() {
     fn dummy_actor_cell() -> ActorCell {
-        ActorCell::new::<TestActor>(None).unwrap().0
+        ActorCell::new::<TestActor>(None, true).unwrap().0
     }
 
     struct TestActor;
@@ -943,7 +1057,7 @@ fn returns_actor_references() {
         ),
         (
             true,
-            SupervisionEvent::ActorTerminated(dummy_actor_cell(), None, Some("Foo!".to_owned())),
+            SupervisionEvent::ActorTerminated(dummy_actor_cell(), None, Some("Foo!".into())),
         ),
         (
             false,
@@ -1156,3 +1270,1485 @@ async fn runtime_message_typing() {
     actor.stop(None);
     handle.await.unwrap();
 }
+
+#[crate::concurrency::test]
+#[tracing_test::traced_test]
+async fn current_message_tracks_in_flight_message() {
+    enum TestMessage {
+        Slow,
+        Slower,
+    }
+    #[cfg(feature = "cluster")]
+    impl crate::Message for TestMessage {}
+
+    struct TestActor;
+
+    #[cfg_attr(feature = "async-trait", crate::async_trait)]
+    impl Actor for TestActor {
+        type Msg = TestMessage;
+        type Arguments = ();
+        type State = ();
+
+        async fn pre_start(
+            &self,
+            _this_actor: crate::ActorRef<Self::Msg>,
+            _: (),
+        ) -> Result<Self::State, ActorProcessingErr> {
+            Ok(())
+        }
+
+        async fn handle(
+            &self,
+            _: ActorRef<Self::Msg>,
+            _: Self::Msg,
+            _: &mut Self::State,
+        ) -> Result<(), ActorProcessingErr> {
+            sleep(Duration::from_millis(200)).await;
+            Ok(())
+        }
+
+        fn message_name(message: &Self::Msg) -> &'static str {
+            match message {
+                TestMessage::Slow => "Slow",
+                TestMessage::Slower => "Slower",
+            }
+        }
+    }
+
+    let (actor, handle) = Actor::spawn(None, TestActor, ())
+        .await
+        .expect("Failed to start test actor");
+    assert_eq!(None, actor.current_message());
+
+    // each message is reported by its own name while it's in-flight
+    actor
+        .cast(TestMessage::Slow)
+        .expect("Failed to send message to actor");
+    actor
+        .cast(TestMessage::Slower)
+        .expect("Failed to send message to actor");
+    periodic_check(
+        || actor.current_message().as_deref() == Some("Slow"),
+        Duration::from_millis(100),
+    )
+    .await;
+    periodic_check(
+        || actor.current_message().as_deref() == Some("Slower"),
+        Duration::from_millis(500),
+    )
+    .await;
+
+    // once handling completes, the in-flight message is cleared
+    periodic_check(
+        || actor.current_message().is_none(),
+        Duration::from_millis(500),
+    )
+    .await;
+
+    // cleanup
+    actor.stop(None);
+    handle.await.unwrap();
+}
+
+#[crate::concurrency::test]
+#[tracing_test::traced_test]
+async fn actor_sender_forwards_messages() {
+    struct TestActor {
+        counter: Arc<AtomicU32>,
+    }
+
+    #[cfg_attr(feature = "async-trait", crate::async_trait)]
+    impl Actor for TestActor {
+        type Msg = EmptyMessage;
+        type Arguments = ();
+        type State = ();
+
+        async fn pre_start(
+            &self,
+            _this_actor: crate::ActorRef<Self::Msg>,
+            _: (),
+        ) -> Result<Self::State, ActorProcessingErr> {
+            Ok(())
+        }
+
+        async fn handle(
+            &self,
+            _: ActorRef<Self::Msg>,
+            _: Self::Msg,
+            _: &mut Self::State,
+        ) -> Result<(), ActorProcessingErr> {
+            self.counter.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    let counter = Arc::new(AtomicU32::new(0));
+    let (actor, handle) = Actor::spawn(
+        None,
+        TestActor {
+            counter: counter.clone(),
+        },
+        (),
+    )
+    .await
+    .expect("Failed to start test actor");
+
+    let sender = actor.sender();
+    assert_eq!(actor.get_id(), sender.get_id());
+    assert!(sender.is_alive());
+
+    for _ in 0..10 {
+        sender
+            .clone()
+            .send(EmptyMessage)
+            .expect("Failed to send message through sender");
+    }
+    periodic_check(
+        || counter.load(Ordering::SeqCst) == 10,
+        Duration::from_millis(500),
+    )
+    .await;
+
+    // cleanup
+    actor.stop(None);
+    handle.await.unwrap();
+
+    // sends to a dead actor fail cleanly, handing the message back
+    assert!(!sender.is_alive());
+    assert!(matches!(
+        sender.send(EmptyMessage),
+        Err(MessagingErr::SendErr(_))
+    ));
+}
+
+#[crate::concurrency::test]
+#[tracing_test::traced_test]
+async fn stop_or_kill_drains_or_escalates() {
+    struct TestActor {
+        counter: Arc<AtomicU32>,
+        delay: Duration,
+    }
+
+    #[cfg_attr(feature = "async-trait", crate::async_trait)]
+    impl Actor for TestActor {
+        type Msg = EmptyMessage;
+        type Arguments = ();
+        type State = ();
+
+        async fn pre_start(
+            &self,
+            _this_actor: crate::ActorRef<Self::Msg>,
+            _: (),
+        ) -> Result<Self::State, ActorProcessingErr> {
+            Ok(())
+        }
+
+        async fn handle(
+            &self,
+            _: ActorRef<Self::Msg>,
+            _: Self::Msg,
+            _: &mut Self::State,
+        ) -> Result<(), ActorProcessingErr> {
+            sleep(self.delay).await;
+            self.counter.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    // fast handler: the queue drains within the timeout
+    let counter = Arc::new(AtomicU32::new(0));
+    let (actor, handle) = Actor::spawn(
+        None,
+        TestActor {
+            counter: counter.clone(),
+            delay: Duration::from_millis(1),
+        },
+        (),
+    )
+    .await
+    .expect("Failed to spawn test actor");
+    for _ in 0..10 {
+        actor
+            .cast(EmptyMessage)
+            .expect("Failed to send message to actor");
+    }
+    let outcome = actor
+        .stop_or_kill(Duration::from_millis(500))
+        .await
+        .expect("Failed to shutdown actor");
+    assert_eq!(crate::ShutdownOutcome::Drained, outcome);
+    assert_eq!(10, counter.load(Ordering::SeqCst));
+    handle.await.unwrap();
+
+    // slow handler: the drain times out and the actor is killed
+    let counter = Arc::new(AtomicU32::new(0));
+    let (actor, handle) = Actor::spawn(
+        None,
+        TestActor {
+            counter: counter.clone(),
+            delay: Duration::from_millis(1000),
+        },
+        (),
+    )
+    .await
+    .expect("Failed to spawn test actor");
+    for _ in 0..10 {
+        actor
+            .cast(EmptyMessage)
+            .expect("Failed to send message to actor");
+    }
+    let outcome = actor
+        .stop_or_kill(Duration::from_millis(50))
+        .await
+        .expect("Failed to shutdown actor");
+    assert_eq!(crate::ShutdownOutcome::Killed, outcome);
+    assert_eq!(0, counter.load(Ordering::SeqCst));
+    handle.await.unwrap();
+    assert_eq!(ActorStatus::Stopped, actor.get_status());
+
+    // shutting down a dead actor is an error
+    assert!(actor.stop_or_kill(Duration::from_millis(50)).await.is_err());
+}
+
+#[crate::concurrency::test]
+#[tracing_test::traced_test]
+async fn stop_or_kill_when_actor_stops_at_the_deadline() {
+    struct TestActor {
+        delay: Duration,
+    }
+
+    #[cfg_attr(feature = "async-trait", crate::async_trait)]
+    impl Actor for TestActor {
+        type Msg = EmptyMessage;
+        type Arguments = ();
+        type State = ();
+
+        async fn pre_start(
+            &self,
+            _this_actor: crate::ActorRef<Self::Msg>,
+            _: (),
+        ) -> Result<Self::State, ActorProcessingErr> {
+            Ok(())
+        }
+
+        async fn handle(
+            &self,
+            _: ActorRef<Self::Msg>,
+            _: Self::Msg,
+            _: &mut Self::State,
+        ) -> Result<(), ActorProcessingErr> {
+            sleep(self.delay).await;
+            Ok(())
+        }
+    }
+
+    // killing an actor which has already stopped returns instead of waiting forever
+    let (actor, handle) = Actor::spawn(
+        None,
+        TestActor {
+            delay: Duration::from_millis(1),
+        },
+        (),
+    )
+    .await
+    .expect("Failed to spawn test actor");
+    actor.stop(None);
+    handle.await.unwrap();
+    let result = crate::concurrency::timeout(Duration::from_millis(500), actor.kill_and_wait(None))
+        .await
+        .expect("kill_and_wait hung on a stopped actor");
+    assert!(result.is_err());
+
+    // the drain finishes right around the deadline, so the escalation to a kill races
+    // the actor stopping on its own. Either outcome is fine, but it must never hang.
+    for _ in 0..20 {
+        let (actor, handle) = Actor::spawn(
+            None,
+            TestActor {
+                delay: Duration::from_millis(20),
+            },
+            (),
+        )
+        .await
+        .expect("Failed to spawn test actor");
+        actor
+            .cast(EmptyMessage)
+            .expect("Failed to send message to actor");
+        let outcome = crate::concurrency::timeout(
+            Duration::from_millis(1000),
+            actor.stop_or_kill(Duration::from_millis(20)),
+        )
+        .await
+        .expect("stop_or_kill hung when the actor stopped at the deadline")
+        .expect("Failed to shutdown actor");
+        assert!(matches!(
+            outcome,
+            crate::ShutdownOutcome::Drained | crate::ShutdownOutcome::Killed
+        ));
+        handle.await.unwrap();
+        assert_eq!(ActorStatus::Stopped, actor.get_status());
+    }
+}
+
+#[crate::concurrency::test]
+#[tracing_test::traced_test]
+async fn redirect_forwards_messages_until_cleared() {
+    struct TestActor {
+        counter: Arc<AtomicU32>,
+    }
+
+    #[cfg_attr(feature = "async-trait", crate::async_trait)]
+    impl Actor for TestActor {
+        type Msg = EmptyMessage;
+        type Arguments = ();
+        type State = ();
+
+        async fn pre_start(
+            &self,
+            _this_actor: crate::ActorRef<Self::Msg>,
+            _: (),
+        ) -> Result<Self::State, ActorProcessingErr> {
+            Ok(())
+        }
+
+        async fn handle(
+            &self,
+            _: ActorRef<Self::Msg>,
+            _: Self::Msg,
+            _: &mut Self::State,
+        ) -> Result<(), ActorProcessingErr> {
+            self.counter.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    let counter_a = Arc::new(AtomicU32::new(0));
+    let counter_b = Arc::new(AtomicU32::new(0));
+    let (actor_a, handle_a) = Actor::spawn(
+        None,
+        TestActor {
+            counter: counter_a.clone(),
+        },
+        (),
+    )
+    .await
+    .expect("Failed to start test actor");
+    let (actor_b, handle_b) = Actor::spawn(
+        None,
+        TestActor {
+            counter: counter_b.clone(),
+        },
+        (),
+    )
+    .await
+    .expect("Failed to start test actor");
+
+    // redirected: messages land on B
+    actor_a
+        .redirect_to(actor_b.get_cell())
+        .expect("Failed to redirect actor");
+    for _ in 0..5 {
+        actor_a
+            .cast(EmptyMessage)
+            .expect("Failed to send message to actor");
+    }
+    periodic_check(
+        || counter_b.load(Ordering::SeqCst) == 5,
+        Duration::from_millis(500),
+    )
+    .await;
+    assert_eq!(0, counter_a.load(Ordering::SeqCst));
+
+    // cleared: messages land back on A
+    actor_a.clear_redirect();
+    for _ in 0..3 {
+        actor_a
+            .cast(EmptyMessage)
+            .expect("Failed to send message to actor");
+    }
+    periodic_check(
+        || counter_a.load(Ordering::SeqCst) == 3,
+        Duration::from_millis(500),
+    )
+    .await;
+    assert_eq!(5, counter_b.load(Ordering::SeqCst));
+
+    // redirecting to a dead actor fails the send rather than falling back to A
+    actor_b.stop(None);
+    handle_b.await.unwrap();
+    actor_a
+        .redirect_to(actor_b.get_cell())
+        .expect("Failed to redirect actor");
+    assert!(matches!(
+        actor_a.cast(EmptyMessage),
+        Err(MessagingErr::SendErr(_))
+    ));
+    actor_a.clear_redirect();
+
+    // cleanup
+    actor_a.stop(None);
+    handle_a.await.unwrap();
+}
+
+#[crate::concurrency::test]
+#[tracing_test::traced_test]
+async fn redirect_rejects_mismatched_message_types() {
+    struct TestActor;
+    struct OtherActor;
+
+    #[cfg_attr(feature = "async-trait", crate::async_trait)]
+    impl Actor for TestActor {
+        type Msg = EmptyMessage;
+        type Arguments = ();
+        type State = ();
+
+        async fn pre_start(
+            &self,
+            _this_actor: crate::ActorRef<Self::Msg>,
+            _: (),
+        ) -> Result<Self::State, ActorProcessingErr> {
+            Ok(())
+        }
+    }
+
+    #[cfg_attr(feature = "async-trait", crate::async_trait)]
+    impl Actor for OtherActor {
+        type Msg = ();
+        type Arguments = ();
+        type State = ();
+
+        async fn pre_start(
+            &self,
+            _this_actor: crate::ActorRef<Self::Msg>,
+            _: (),
+        ) -> Result<Self::State, ActorProcessingErr> {
+            Ok(())
+        }
+    }
+
+    let (actor, handle) = Actor::spawn(None, TestActor, ())
+        .await
+        .expect("Failed to start test actor");
+    let (other, other_handle) = Actor::spawn(None, OtherActor, ())
+        .await
+        .expect("Failed to start test actor");
+
+    assert!(matches!(
+        actor.redirect_to(other.get_cell()),
+        Err(MessagingErr::InvalidActorType)
+    ));
+    actor
+        .cast(EmptyMessage)
+        .expect("Failed to send message to actor");
+
+    // cleanup
+    actor.stop(None);
+    other.stop(None);
+    handle.await.unwrap();
+    other_handle.await.unwrap();
+}
+
+#[cfg(feature = "cluster")]
+#[crate::concurrency::test]
+#[tracing_test::traced_test]
+async fn redirect_rejects_remote_targets() {
+    use crate::{ActorId, ActorRuntime};
+
+    struct TestActor;
+
+    #[cfg_attr(feature = "async-trait", crate::async_trait)]
+    impl Actor for TestActor {
+        type Msg = EmptyMessage;
+        type Arguments = ();
+        type State = ();
+
+        async fn pre_start(
+            &self,
+            _this_actor: crate::ActorRef<Self::Msg>,
+            _: (),
+        ) -> Result<Self::State, ActorProcessingErr> {
+            Ok(())
+        }
+    }
+
+    let (actor, handle) = Actor::spawn(None, TestActor, ())
+        .await
+        .expect("Failed to start test actor");
+    let (remote, remote_handle) = ActorRuntime::spawn_linked_remote(
+        None,
+        TestActor,
+        ActorId::Remote { node_id: 1, pid: 1 },
+        (),
+        actor.get_cell(),
+    )
+    .await
+    .expect("Failed to spawn remote actor");
+
+    // the remote actor's message type can't be checked, even if it would match
+    assert!(matches!(
+        actor.redirect_to(remote.get_cell()),
+        Err(MessagingErr::InvalidActorType)
+    ));
+
+    // cleanup
+    actor.stop(None);
+    handle.await.unwrap();
+    remote_handle.await.unwrap();
+}
+
+#[crate::concurrency::test]
+#[tracing_test::traced_test]
+async fn redirect_rejects_cycles() {
+    struct TestActor;
+
+    #[cfg_attr(feature = "async-trait", crate::async_trait)]
+    impl Actor for TestActor {
+        type Msg = EmptyMessage;
+        type Arguments = ();
+        type State = ();
+
+        async fn pre_start(
+            &self,
+            _this_actor: crate::ActorRef<Self::Msg>,
+            _: (),
+        ) -> Result<Self::State, ActorProcessingErr> {
+            Ok(())
+        }
+    }
+
+    let mut actors = vec![];
+    for _ in 0..3 {
+        actors.push(
+            Actor::spawn(None, TestActor, ())
+                .await
+                .expect("Failed to start test actor"),
+        );
+    }
+    let a = actors[0].0.get_cell();
+    let b = actors[1].0.get_cell();
+    let c = actors[2].0.get_cell();
+
+    assert!(matches!(
+        a.redirect_to(a.clone()),
+        Err(MessagingErr::RedirectCycle)
+    ));
+
+    a.redirect_to(b.clone()).expect("Failed to redirect actor");
+    assert!(matches!(
+        b.redirect_to(a.clone()),
+        Err(MessagingErr::RedirectCycle)
+    ));
+
+    // longer cycles are caught too
+    b.redirect_to(c.clone()).expect("Failed to redirect actor");
+    assert!(matches!(
+        c.redirect_to(a.clone()),
+        Err(MessagingErr::RedirectCycle)
+    ));
+
+    // once the chain is broken, the reverse redirect is allowed
+    a.clear_redirect();
+    c.redirect_to(a.clone()).expect("Failed to redirect actor");
+
+    // cleanup
+    for (actor, handle) in actors {
+        actor.stop(None);
+        handle.await.unwrap();
+    }
+}
+
+#[crate::concurrency::test]
+#[tracing_test::traced_test]
+async fn actor_locals_are_shared_across_handlers() {
+    #[derive(Clone, Debug, PartialEq)]
+    struct RequestContext(u32);
+
+    struct TestActor;
+
+    #[cfg_attr(feature = "async-trait", crate::async_trait)]
+    impl Actor for TestActor {
+        type Msg = u32;
+        type Arguments = ();
+        type State = ();
+
+        async fn pre_start(
+            &self,
+            myself: crate::ActorRef<Self::Msg>,
+            _: (),
+        ) -> Result<Self::State, ActorProcessingErr> {
+            myself.set_local(RequestContext(0));
+            Ok(())
+        }
+
+        async fn handle(
+            &self,
+            myself: ActorRef<Self::Msg>,
+            message: Self::Msg,
+            _: &mut Self::State,
+        ) -> Result<(), ActorProcessingErr> {
+            let RequestContext(total) = myself
+                .get_local::<RequestContext>()
+                .expect("Missing actor-local");
+            myself.set_local(RequestContext(total + message));
+            Ok(())
+        }
+    }
+
+    let (actor, handle) = Actor::spawn(None, TestActor, ())
+        .await
+        .expect("Failed to start test actor");
+
+    for i in 1..=4 {
+        actor.cast(i).expect("Failed to send message to actor");
+    }
+    periodic_check(
+        || actor.get_local::<RequestContext>() == Some(RequestContext(10)),
+        Duration::from_millis(500),
+    )
+    .await;
+
+    // locals are keyed by type
+    assert_eq!(None, actor.set_local(5u64));
+    assert_eq!(Some(5u64), actor.set_local(6u64));
+    assert_eq!(Some(6u64), actor.remove_local::<u64>());
+    assert_eq!(None, actor.get_local::<u64>());
+
+    // locals are cleared when the actor stops
+    actor.stop(None);
+    handle.await.unwrap();
+    assert_eq!(None, actor.get_local::<RequestContext>());
+}
+
+#[crate::concurrency::test]
+#[tracing_test::traced_test]
+async fn hot_restart_hands_over_without_dropping_messages() {
+    struct TestActor {
+        counter: Arc<AtomicU32>,
+    }
+
+    #[cfg_attr(feature = "async-trait", crate::async_trait)]
+    impl Actor for TestActor {
+        type Msg = EmptyMessage;
+        type Arguments = ();
+        type State = ();
+
+        async fn pre_start(
+            &self,
+            _this_actor: crate::ActorRef<Self::Msg>,
+            _: (),
+        ) -> Result<Self::State, ActorProcessingErr> {
+            Ok(())
+        }
+
+        async fn handle(
+            &self,
+            _: ActorRef<Self::Msg>,
+            _: Self::Msg,
+            _: &mut Self::State,
+        ) -> Result<(), ActorProcessingErr> {
+            sleep(Duration::from_millis(1)).await;
+            self.counter.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    struct Supervisor {
+        terminated: Arc<AtomicU32>,
+    }
+
+    #[cfg_attr(feature = "async-trait", crate::async_trait)]
+    impl Actor for Supervisor {
+        type Msg = ();
+        type Arguments = ();
+        type State = ();
+
+        async fn pre_start(
+            &self,
+            _this_actor: crate::ActorRef<Self::Msg>,
+            _: (),
+        ) -> Result<Self::State, ActorProcessingErr> {
+            Ok(())
+        }
+
+        async fn handle_supervisor_evt(
+            &self,
+            _: ActorRef<Self::Msg>,
+            message: SupervisionEvent,
+            _: &mut Self::State,
+        ) -> Result<(), ActorProcessingErr> {
+            if let SupervisionEvent::ActorTerminated(..) = message {
+                self.terminated.fetch_add(1, Ordering::SeqCst);
+            }
+            Ok(())
+        }
+    }
+
+    let terminated = Arc::new(AtomicU32::new(0));
+    let (supervisor, s_handle) = Actor::spawn(
+        None,
+        Supervisor {
+            terminated: terminated.clone(),
+        },
+        (),
+    )
+    .await
+    .expect("Failed to start supervisor");
+
+    let old_counter = Arc::new(AtomicU32::new(0));
+    let new_counter = Arc::new(AtomicU32::new(0));
+    let (actor, handle) = Actor::spawn_linked(
+        Some("hot_restart_actor".to_string()),
+        TestActor {
+            counter: old_counter.clone(),
+        },
+        (),
+        supervisor.get_cell(),
+    )
+    .await
+    .expect("Failed to start test actor");
+
+    for _ in 0..10 {
+        actor
+            .cast(EmptyMessage)
+            .expect("Failed to send message to actor");
+    }
+
+    // a replacement must handle the same message type
+    assert!(matches!(
+        actor
+            .hot_restart(
+                Supervisor {
+                    terminated: terminated.clone()
+                },
+                (),
+                Duration::from_millis(500)
+            )
+            .await,
+        Err(RactorErr::Messaging(MessagingErr::InvalidActorType))
+    ));
+
+    let (replacement, r_handle) = actor
+        .hot_restart(
+            TestActor {
+                counter: new_counter.clone(),
+            },
+            (),
+            Duration::from_millis(500),
+        )
+        .await
+        .expect("Failed to hot-restart actor");
+    handle.await.unwrap();
+
+    // the old instance drained its backlog before stopping
+    assert_eq!(10, old_counter.load(Ordering::SeqCst));
+    assert_eq!(ActorStatus::Stopped, actor.get_status());
+
+    // the name was handed over, and outlives the old instance's exit
+    assert_eq!(
+        Some(replacement.get_id()),
+        crate::registry::where_is("hot_restart_actor".to_string()).map(|a| a.get_id())
+    );
+
+    // senders holding the old reference don't observe failures
+    for _ in 0..5 {
+        actor
+            .cast(EmptyMessage)
+            .expect("Failed to send message to actor");
+    }
+    periodic_check(
+        || new_counter.load(Ordering::SeqCst) == 5,
+        Duration::from_millis(500),
+    )
+    .await;
+
+    // the old instance's exit wasn't reported to the supervisor, but the replacement's is
+    assert_eq!(0, terminated.load(Ordering::SeqCst));
+    replacement.stop(None);
+    r_handle.await.unwrap();
+    periodic_check(
+        || terminated.load(Ordering::SeqCst) == 1,
+        Duration::from_millis(500),
+    )
+    .await;
+
+    // cleanup
+    supervisor.stop(None);
+    s_handle.await.unwrap();
+}
+
+#[crate::concurrency::test]
+#[tracing_test::traced_test]
+async fn hot_restart_keeps_name_during_replacement_startup() {
+    struct TestActor {
+        startup: Duration,
+        starting: Arc<AtomicU8>,
+    }
+
+    #[cfg_attr(feature = "async-trait", crate::async_trait)]
+    impl Actor for TestActor {
+        type Msg = EmptyMessage;
+        type Arguments = ();
+        type State = ();
+
+        async fn pre_start(
+            &self,
+            _this_actor: crate::ActorRef<Self::Msg>,
+            _: (),
+        ) -> Result<Self::State, ActorProcessingErr> {
+            self.starting.fetch_add(1, Ordering::SeqCst);
+            sleep(self.startup).await;
+            Ok(())
+        }
+    }
+
+    let name = "hot_restart_startup_actor".to_string();
+    let starting = Arc::new(AtomicU8::new(0));
+    let (actor, handle) = Actor::spawn(
+        Some(name.clone()),
+        TestActor {
+            startup: Duration::from_millis(0),
+            starting: starting.clone(),
+        },
+        (),
+    )
+    .await
+    .expect("Failed to start test actor");
+
+    let restarting = actor.get_cell();
+    let replacing = starting.clone();
+    let restart = crate::concurrency::spawn(async move {
+        restarting
+            .hot_restart(
+                TestActor {
+                    startup: Duration::from_millis(200),
+                    starting: replacing,
+                },
+                (),
+                Duration::from_millis(100),
+            )
+            .await
+    });
+    periodic_check(
+        || starting.load(Ordering::SeqCst) == 2,
+        Duration::from_millis(500),
+    )
+    .await;
+
+    // while the replacement is starting up, the name still resolves to this actor and
+    // can't be taken by another actor
+    assert_eq!(
+        Some(actor.get_id()),
+        crate::registry::where_is(name.clone()).map(|a| a.get_id())
+    );
+    assert!(matches!(
+        Actor::spawn(
+            Some(name.clone()),
+            TestActor {
+                startup: Duration::from_millis(0),
+                starting: starting.clone(),
+            },
+            (),
+        )
+        .await,
+        Err(SpawnErr::ActorAlreadyRegistered(_))
+    ));
+
+    let (replacement, r_handle) = restart.await.unwrap().expect("Failed to hot-restart actor");
+    handle.await.unwrap();
+    assert_eq!(
+        Some(replacement.get_id()),
+        crate::registry::where_is(name.clone()).map(|a| a.get_id())
+    );
+
+    // cleanup
+    replacement.stop(None);
+    r_handle.await.unwrap();
+    assert!(crate::registry::where_is(name).is_none());
+}
+
+#[crate::concurrency::test]
+#[tracing_test::traced_test]
+async fn hot_restart_twice_still_reaches_latest_replacement() {
+    struct TestActor {
+        counter: Arc<AtomicU32>,
+    }
+
+    #[cfg_attr(feature = "async-trait", crate::async_trait)]
+    impl Actor for TestActor {
+        type Msg = EmptyMessage;
+        type Arguments = ();
+        type State = ();
+
+        async fn pre_start(
+            &self,
+            _this_actor: crate::ActorRef<Self::Msg>,
+            _: (),
+        ) -> Result<Self::State, ActorProcessingErr> {
+            Ok(())
+        }
+
+        async fn handle(
+            &self,
+            _: ActorRef<Self::Msg>,
+            _: Self::Msg,
+            _: &mut Self::State,
+        ) -> Result<(), ActorProcessingErr> {
+            self.counter.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    let counter = Arc::new(AtomicU32::new(0));
+    let (original, handle) = Actor::spawn(
+        None,
+        TestActor {
+            counter: Arc::new(AtomicU32::new(0)),
+        },
+        (),
+    )
+    .await
+    .expect("Failed to start test actor");
+
+    // original -> first -> second
+    let (first, first_handle) = original
+        .hot_restart(
+            TestActor {
+                counter: Arc::new(AtomicU32::new(0)),
+            },
+            (),
+            Duration::from_millis(500),
+        )
+        .await
+        .expect("Failed to hot-restart actor");
+    handle.await.unwrap();
+    let (second, second_handle) = first
+        .hot_restart(
+            TestActor {
+                counter: counter.clone(),
+            },
+            (),
+            Duration::from_millis(500),
+        )
+        .await
+        .expect("Failed to hot-restart actor");
+    first_handle.await.unwrap();
+    assert_eq!(ActorStatus::Stopped, first.get_status());
+
+    // senders holding the original reference reach the latest replacement
+    for _ in 0..5 {
+        original
+            .cast(EmptyMessage)
+            .expect("Failed to send message to actor");
+    }
+    periodic_check(
+        || counter.load(Ordering::SeqCst) == 5,
+        Duration::from_millis(500),
+    )
+    .await;
+
+    // cleanup
+    second.stop(None);
+    second_handle.await.unwrap();
+}
+
+#[crate::concurrency::test]
+#[tracing_test::traced_test]
+async fn hot_restart_hands_children_over_to_replacement() {
+    struct TestActor {
+        handle_time: Duration,
+    }
+
+    #[cfg_attr(feature = "async-trait", crate::async_trait)]
+    impl Actor for TestActor {
+        type Msg = EmptyMessage;
+        type Arguments = ();
+        type State = ();
+
+        async fn pre_start(
+            &self,
+            _this_actor: crate::ActorRef<Self::Msg>,
+            _: (),
+        ) -> Result<Self::State, ActorProcessingErr> {
+            Ok(())
+        }
+
+        async fn handle(
+            &self,
+            _: ActorRef<Self::Msg>,
+            _: Self::Msg,
+            _: &mut Self::State,
+        ) -> Result<(), ActorProcessingErr> {
+            sleep(self.handle_time).await;
+            Ok(())
+        }
+    }
+
+    struct Child;
+
+    #[cfg_attr(feature = "async-trait", crate::async_trait)]
+    impl Actor for Child {
+        type Msg = ();
+        type Arguments = ();
+        type State = ();
+
+        async fn pre_start(
+            &self,
+            _this_actor: crate::ActorRef<Self::Msg>,
+            _: (),
+        ) -> Result<Self::State, ActorProcessingErr> {
+            Ok(())
+        }
+    }
+
+    // the old instance either drains in time, or is killed once the drain times out
+    for handle_time in [Duration::from_millis(1), Duration::from_millis(2000)] {
+        let (actor, handle) = Actor::spawn(None, TestActor { handle_time }, ())
+            .await
+            .expect("Failed to start test actor");
+        let (child, c_handle) = Actor::spawn_linked(None, Child, (), actor.get_cell())
+            .await
+            .expect("Failed to start child actor");
+
+        actor
+            .cast(EmptyMessage)
+            .expect("Failed to send message to actor");
+        let (replacement, r_handle) = actor
+            .hot_restart(
+                TestActor {
+                    handle_time: Duration::from_millis(1),
+                },
+                (),
+                Duration::from_millis(100),
+            )
+            .await
+            .expect("Failed to hot-restart actor");
+        handle.await.unwrap();
+        assert_eq!(ActorStatus::Stopped, actor.get_status());
+
+        // the child outlived the old instance, and is supervised by the replacement
+        assert_eq!(ActorStatus::Running, child.get_status());
+        assert_eq!(
+            Some(replacement.get_id()),
+            child.get_supervisor().map(|s| s.get_id())
+        );
+        assert_eq!(1, replacement.get_num_children());
+        assert_eq!(0, actor.get_num_children());
+
+        // the child now exits along with the replacement
+        replacement.stop(None);
+        r_handle.await.unwrap();
+        c_handle.await.unwrap();
+    }
+}
+
+#[crate::concurrency::test]
+#[tracing_test::traced_test]
+async fn mailbox_byte_budget_rejects_oversized_backlog() {
+    struct TestActor {
+        counter: Arc<AtomicU32>,
+    }
+
+    struct Frame(Vec<u8>);
+    #[cfg(feature = "cluster")]
+    impl crate::Message for Frame {}
+
+    #[cfg_attr(feature = "async-trait", crate::async_trait)]
+    impl Actor for TestActor {
+        type Msg = Frame;
+        type Arguments = ();
+        type State = ();
+
+        async fn pre_start(
+            &self,
+            _this_actor: crate::ActorRef<Self::Msg>,
+            _: (),
+        ) -> Result<Self::State, ActorProcessingErr> {
+            Ok(())
+        }
+
+        async fn handle(
+            &self,
+            _: ActorRef<Self::Msg>,
+            _: Self::Msg,
+            _: &mut Self::State,
+        ) -> Result<(), ActorProcessingErr> {
+            sleep(Duration::from_millis(100)).await;
+            self.counter.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+
+        fn message_weight(message: &Self::Msg) -> usize {
+            message.0.len()
+        }
+
+        fn max_total_mailbox_bytes(&self) -> Option<usize> {
+            Some(100)
+        }
+    }
+
+    let counter = Arc::new(AtomicU32::new(0));
+    let (actor, handle) = Actor::spawn(
+        None,
+        TestActor {
+            counter: counter.clone(),
+        },
+        (),
+    )
+    .await
+    .expect("Failed to start test actor");
+
+    // the first frame is picked up immediately, releasing its weight
+    actor
+        .cast(Frame(vec![0; 10]))
+        .expect("Failed to send message to actor");
+    periodic_check(|| actor.mailbox_bytes() == 0, Duration::from_millis(500)).await;
+
+    actor
+        .cast(Frame(vec![0; 60]))
+        .expect("Failed to send message to actor");
+    assert_eq!(60, actor.mailbox_bytes());
+
+    // this frame would exceed the budget, and is handed back
+    match actor.cast(Frame(vec![0; 50])) {
+        Err(MessagingErr::MailboxBudgetExceeded(Frame(payload))) => assert_eq!(50, payload.len()),
+        other => panic!("Expected the mailbox budget to be exceeded, got {other:?}"),
+    }
+    actor
+        .cast(Frame(vec![0; 40]))
+        .expect("Failed to send message to actor");
+    assert_eq!(100, actor.mailbox_bytes());
+
+    // once the backlog is processed, the budget is available again
+    periodic_check(
+        || counter.load(Ordering::SeqCst) == 3,
+        Duration::from_millis(1000),
+    )
+    .await;
+    assert_eq!(0, actor.mailbox_bytes());
+
+    // cleanup
+    actor.stop(None);
+    handle.await.unwrap();
+}
+
+#[cfg(feature = "latency_histogram")]
+#[crate::concurrency::test]
+#[tracing_test::traced_test]
+async fn latency_snapshot_reports_handling_percentiles() {
+    struct TestActor {
+        tracked: bool,
+    }
+
+    #[cfg_attr(feature = "async-trait", crate::async_trait)]
+    impl Actor for TestActor {
+        type Msg = EmptyMessage;
+        type Arguments = ();
+        type State = ();
+
+        async fn pre_start(
+            &self,
+            _this_actor: crate::ActorRef<Self::Msg>,
+            _: (),
+        ) -> Result<Self::State, ActorProcessingErr> {
+            Ok(())
+        }
+
+        async fn handle(
+            &self,
+            _: ActorRef<Self::Msg>,
+            _: Self::Msg,
+            _: &mut Self::State,
+        ) -> Result<(), ActorProcessingErr> {
+            sleep(Duration::from_millis(10)).await;
+            Ok(())
+        }
+
+        fn track_handling_latency(&self) -> bool {
+            self.tracked
+        }
+    }
+
+    let (untracked, untracked_handle) = Actor::spawn(None, TestActor { tracked: false }, ())
+        .await
+        .expect("Failed to start test actor");
+    assert!(untracked.latency_snapshot().is_none());
+
+    let (actor, handle) = Actor::spawn(None, TestActor { tracked: true }, ())
+        .await
+        .expect("Failed to start test actor");
+    let empty = actor
+        .latency_snapshot()
+        .expect("Latency tracking should be enabled");
+    assert_eq!(0, empty.count());
+    assert_eq!(Duration::ZERO, empty.p99());
+
+    for _ in 0..5 {
+        actor
+            .cast(EmptyMessage)
+            .expect("Failed to send message to actor");
+    }
+    periodic_check(
+        || actor.latency_snapshot().map(|s| s.count()) == Some(5),
+        Duration::from_millis(500),
+    )
+    .await;
+
+    let snapshot = actor.latency_snapshot().unwrap();
+    assert!(snapshot.mean() >= Duration::from_millis(10));
+    assert!(snapshot.p50() >= Duration::from_millis(10));
+    // percentiles are bucket upper bounds, capped at the exact maximum
+    assert!(snapshot.p50() <= snapshot.p99());
+    assert!(snapshot.p999() <= snapshot.max());
+
+    // cleanup
+    actor.stop(None);
+    untracked.stop(None);
+    handle.await.unwrap();
+    untracked_handle.await.unwrap();
+}
+
+#[crate::concurrency::test]
+#[tracing_test::traced_test]
+async fn send_with_only_builds_messages_for_live_actors() {
+    struct TestActor {
+        counter: Arc<AtomicU32>,
+    }
+
+    #[cfg_attr(feature = "async-trait", crate::async_trait)]
+    impl Actor for TestActor {
+        type Msg = EmptyMessage;
+        type Arguments = ();
+        type State = ();
+
+        async fn pre_start(
+            &self,
+            _this_actor: crate::ActorRef<Self::Msg>,
+            _: (),
+        ) -> Result<Self::State, ActorProcessingErr> {
+            Ok(())
+        }
+
+        async fn handle(
+            &self,
+            _: ActorRef<Self::Msg>,
+            _: Self::Msg,
+            _: &mut Self::State,
+        ) -> Result<(), ActorProcessingErr> {
+            self.counter.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    let counter = Arc::new(AtomicU32::new(0));
+    let built = Arc::new(AtomicU32::new(0));
+    let build = || {
+        built.fetch_add(1, Ordering::SeqCst);
+        EmptyMessage
+    };
+
+    let (actor, handle) = Actor::spawn(
+        None,
+        TestActor {
+            counter: counter.clone(),
+        },
+        (),
+    )
+    .await
+    .expect("Failed to start test actor");
+
+    actor
+        .send_with(build)
+        .expect("Failed to send message to actor");
+    periodic_check(
+        || counter.load(Ordering::SeqCst) == 1,
+        Duration::from_millis(500),
+    )
+    .await;
+    assert_eq!(1, built.load(Ordering::SeqCst));
+
+    actor.stop(None);
+    handle.await.unwrap();
+
+    // the actor is dead, so the message is never built
+    assert!(matches!(
+        actor.send_with(build),
+        Err(MessagingErr::ChannelClosed)
+    ));
+    assert_eq!(1, built.load(Ordering::SeqCst));
+}
+
+#[crate::concurrency::test]
+#[tracing_test::traced_test]
+async fn deferred_readiness_waits_for_mark_ready() {
+    struct TestActor {
+        deferred: bool,
+    }
+
+    #[cfg_attr(feature = "async-trait", crate::async_trait)]
+    impl Actor for TestActor {
+        type Msg = EmptyMessage;
+        type Arguments = ();
+        type State = ();
+
+        async fn pre_start(
+            &self,
+            _this_actor: crate::ActorRef<Self::Msg>,
+            _: (),
+        ) -> Result<Self::State, ActorProcessingErr> {
+            Ok(())
+        }
+
+        async fn handle(
+            &self,
+            myself: ActorRef<Self::Msg>,
+            _: Self::Msg,
+            _: &mut Self::State,
+        ) -> Result<(), ActorProcessingErr> {
+            // warmup is complete
+            myself.mark_ready();
+            Ok(())
+        }
+
+        fn defer_readiness(&self) -> bool {
+            self.deferred
+        }
+    }
+
+    let (plain, plain_handle) = Actor::spawn(None, TestActor { deferred: false }, ())
+        .await
+        .expect("Failed to start test actor");
+    periodic_check(|| plain.is_ready(), Duration::from_millis(500)).await;
+
+    let (actor, handle) = Actor::spawn(None, TestActor { deferred: true }, ())
+        .await
+        .expect("Failed to start test actor");
+    periodic_check(
+        || actor.get_status() == ActorStatus::Running,
+        Duration::from_millis(500),
+    )
+    .await;
+    assert!(!actor.is_ready());
+
+    let waiter = {
+        let actor = actor.get_cell();
+        crate::concurrency::spawn(async move { actor.wait_for_ready().await })
+    };
+    sleep(Duration::from_millis(50)).await;
+    assert!(!waiter.is_finished());
+
+    actor
+        .cast(EmptyMessage)
+        .expect("Failed to send message to actor");
+    assert!(
+        crate::concurrency::timeout(Duration::from_millis(500), waiter)
+            .await
+            .expect("Timed out waiting for readiness")
+            .unwrap()
+    );
+    assert!(actor.is_ready());
+
+    // a stopped actor is no longer ready, and waiters don't hang
+    actor.stop(None);
+    handle.await.unwrap();
+    assert!(!actor.is_ready());
+    assert!(!actor.wait_for_ready().await);
+
+    plain.stop(None);
+    plain_handle.await.unwrap();
+}
+
+#[crate::concurrency::test]
+#[tracing_test::traced_test]
+async fn send_when_ready_waits_for_mailbox_capacity() {
+    struct TestActor {
+        counter: Arc<AtomicU32>,
+    }
+
+    struct Frame(Vec<u8>);
+    #[cfg(feature = "cluster")]
+    impl crate::Message for Frame {}
+
+    #[cfg_attr(feature = "async-trait", crate::async_trait)]
+    impl Actor for TestActor {
+        type Msg = Frame;
+        type Arguments = ();
+        type State = ();
+
+        async fn pre_start(
+            &self,
+            _this_actor: crate::ActorRef<Self::Msg>,
+            _: (),
+        ) -> Result<Self::State, ActorProcessingErr> {
+            Ok(())
+        }
+
+        async fn handle(
+            &self,
+            _: ActorRef<Self::Msg>,
+            _: Self::Msg,
+            _: &mut Self::State,
+        ) -> Result<(), ActorProcessingErr> {
+            sleep(Duration::from_millis(100)).await;
+            self.counter.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+
+        fn message_weight(message: &Self::Msg) -> usize {
+            message.0.len()
+        }
+
+        fn max_total_mailbox_bytes(&self) -> Option<usize> {
+            Some(100)
+        }
+    }
+
+    let counter = Arc::new(AtomicU32::new(0));
+    let (actor, handle) = Actor::spawn(
+        None,
+        TestActor {
+            counter: counter.clone(),
+        },
+        (),
+    )
+    .await
+    .expect("Failed to start test actor");
+
+    // the first frame is picked up immediately, the second fills the mailbox
+    actor
+        .cast(Frame(vec![0; 60]))
+        .expect("Failed to send message to actor");
+    periodic_check(|| actor.mailbox_bytes() == 0, Duration::from_millis(500)).await;
+    actor
+        .cast(Frame(vec![0; 60]))
+        .expect("Failed to send message to actor");
+    assert!(matches!(
+        actor.cast(Frame(vec![0; 60])),
+        Err(MessagingErr::MailboxBudgetExceeded(_))
+    ));
+
+    // waits for the second frame to be picked up, rather than failing
+    actor
+        .send_when_ready(Frame(vec![0; 60]))
+        .await
+        .expect("Failed to send message to actor");
+    assert!(counter.load(Ordering::SeqCst) >= 1);
+    assert_eq!(60, actor.mailbox_bytes());
+
+    // a frame which could never fit is rejected rather than waiting forever
+    assert!(matches!(
+        actor.send_when_ready(Frame(vec![0; 200])).await,
+        Err(MessagingErr::MailboxBudgetExceeded(_))
+    ));
+
+    periodic_check(
+        || counter.load(Ordering::SeqCst) == 3,
+        Duration::from_millis(1000),
+    )
+    .await;
+    assert!(actor.wait_for_mailbox_capacity().await);
+
+    // cleanup
+    actor.stop(None);
+    handle.await.unwrap();
+    assert!(!actor.wait_for_mailbox_capacity().await);
+}
@@ -1560,3 +1560,495 @@ async fn draining_children_will_shutdown_parent_too() {
     // Child's post-stop should have been called.
     assert_eq!(1, flag.load(Ordering::Relaxed));
 }
+
+#[crate::concurrency::test]
+#[tracing_test::traced_test]
+async fn test_restart_policy_decides_on_failure_reason() {
+    use crate::actor::supervision::{
+        ChildExitReason, DefaultRestartPolicy, RestartDecision, RestartPolicy,
+    };
+
+    #[derive(Debug)]
+    enum ChildError {
+        ConnectionRefused,
+        ConfigInvalid,
+    }
+    impl std::fmt::Display for ChildError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{self:?}")
+        }
+    }
+    impl std::error::Error for ChildError {}
+
+    struct Child;
+
+    #[cfg_attr(feature = "async-trait", crate::async_trait)]
+    impl Actor for Child {
+        type Msg = bool;
+        type State = ();
+        type Arguments = ();
+        async fn pre_start(
+            &self,
+            _this_actor: ActorRef<Self::Msg>,
+            _: (),
+        ) -> Result<Self::State, ActorProcessingErr> {
+            Ok(())
+        }
+        async fn handle(
+            &self,
+            _this_actor: ActorRef<Self::Msg>,
+            fatal: Self::Msg,
+            _state: &mut Self::State,
+        ) -> Result<(), ActorProcessingErr> {
+            if fatal {
+                Err(Box::new(ChildError::ConfigInvalid))
+            } else {
+                Err(Box::new(ChildError::ConnectionRefused))
+            }
+        }
+    }
+
+    struct Supervisor {
+        restarts: Arc<AtomicU8>,
+        child: Arc<std::sync::Mutex<Option<ActorRef<bool>>>>,
+    }
+
+    #[cfg_attr(feature = "async-trait", crate::async_trait)]
+    impl Actor for Supervisor {
+        type Msg = ();
+        type State = ();
+        type Arguments = ();
+        async fn pre_start(
+            &self,
+            myself: ActorRef<Self::Msg>,
+            _: (),
+        ) -> Result<Self::State, ActorProcessingErr> {
+            let (child, _) = Actor::spawn_linked(None, Child, (), myself.get_cell()).await?;
+            *self.child.lock().unwrap() = Some(child);
+            Ok(())
+        }
+
+        async fn handle_supervisor_evt(
+            &self,
+            myself: ActorRef<Self::Msg>,
+            message: SupervisionEvent,
+            _state: &mut Self::State,
+        ) -> Result<(), ActorProcessingErr> {
+            let policy = |reason: &ChildExitReason<'_>| match reason {
+                ChildExitReason::Failed(err) => match err.downcast_ref::<ChildError>() {
+                    Some(ChildError::ConnectionRefused) => RestartDecision::Restart,
+                    _ => RestartDecision::Escalate,
+                },
+                other => DefaultRestartPolicy.decide(other),
+            };
+            match policy.decide_event(&message) {
+                Some(RestartDecision::Restart) => {
+                    self.restarts.fetch_add(1, Ordering::SeqCst);
+                    let (child, _) =
+                        Actor::spawn_linked(None, Child, (), myself.get_cell()).await?;
+                    *self.child.lock().unwrap() = Some(child);
+                }
+                Some(RestartDecision::Escalate) => {
+                    return Err(From::from("Child failed fatally, escalating"));
+                }
+                Some(RestartDecision::Stop) | None => {}
+            }
+            Ok(())
+        }
+    }
+
+    let restarts = Arc::new(AtomicU8::new(0));
+    let child = Arc::new(std::sync::Mutex::new(None));
+    let (supervisor_ref, s_handle) = Actor::spawn(
+        None,
+        Supervisor {
+            restarts: restarts.clone(),
+            child: child.clone(),
+        },
+        (),
+    )
+    .await
+    .expect("Supervisor panicked on startup");
+
+    // transient failures are restarted
+    for i in 1..=2u8 {
+        let current = child.lock().unwrap().clone().unwrap();
+        current.cast(false).expect("Failed to send to child");
+        periodic_check(
+            || restarts.load(Ordering::SeqCst) == i,
+            Duration::from_millis(500),
+        )
+        .await;
+    }
+    assert_eq!(ActorStatus::Running, supervisor_ref.get_status());
+
+    // fatal failures escalate, failing the supervisor
+    let current = child.lock().unwrap().clone().unwrap();
+    current.cast(true).expect("Failed to send to child");
+    s_handle.await.unwrap();
+    assert_eq!(2, restarts.load(Ordering::SeqCst));
+    assert_eq!(ActorStatus::Stopped, supervisor_ref.get_status());
+}
+
+#[test]
+fn test_default_restart_policy() {
+    use crate::actor::supervision::{
+        ChildExitReason, DefaultRestartPolicy, RestartDecision, RestartPolicy,
+    };
+
+    let policy = DefaultRestartPolicy;
+    assert_eq!(
+        RestartDecision::Stop,
+        policy.decide(&ChildExitReason::Stopped(None))
+    );
+    assert_eq!(
+        RestartDecision::Stop,
+        policy.decide(&ChildExitReason::Stopped(Some(
+            &crate::StopReason::Supervisor
+        )))
+    );
+    assert_eq!(
+        RestartDecision::Stop,
+        policy.decide(&ChildExitReason::Stopped(Some(&crate::StopReason::Normal)))
+    );
+    assert_eq!(
+        RestartDecision::Restart,
+        policy.decide(&ChildExitReason::Killed)
+    );
+    let err: ActorProcessingErr = From::from("boom");
+    assert_eq!(
+        RestartDecision::Restart,
+        policy.decide(&ChildExitReason::Failed(&err))
+    );
+    // a child stopped with an error reason exited abnormally
+    let reason = crate::StopReason::from(err);
+    assert_eq!(
+        RestartDecision::Restart,
+        policy.decide(&ChildExitReason::Stopped(Some(&reason)))
+    );
+}
+
+#[crate::concurrency::test]
+#[tracing_test::traced_test]
+async fn test_supervision_events_batched_within_window() {
+    struct Child;
+
+    #[cfg_attr(feature = "async-trait", crate::async_trait)]
+    impl Actor for Child {
+        type Msg = ();
+        type State = ();
+        type Arguments = ();
+        async fn pre_start(
+            &self,
+            _this_actor: ActorRef<Self::Msg>,
+            _: (),
+        ) -> Result<Self::State, ActorProcessingErr> {
+            Ok(())
+        }
+        async fn handle(
+            &self,
+            _this_actor: ActorRef<Self::Msg>,
+            _message: Self::Msg,
+            _state: &mut Self::State,
+        ) -> Result<(), ActorProcessingErr> {
+            Err(From::from("boom"))
+        }
+    }
+
+    struct Supervisor {
+        // the number of failures delivered in each batch
+        batches: Arc<std::sync::Mutex<Vec<usize>>>,
+    }
+
+    #[cfg_attr(feature = "async-trait", crate::async_trait)]
+    impl Actor for Supervisor {
+        type Msg = ();
+        type State = ();
+        type Arguments = ();
+        async fn pre_start(
+            &self,
+            _this_actor: ActorRef<Self::Msg>,
+            _: (),
+        ) -> Result<Self::State, ActorProcessingErr> {
+            Ok(())
+        }
+
+        fn supervision_batch_window(&self) -> Option<Duration> {
+            Some(Duration::from_millis(100))
+        }
+
+        async fn handle_supervisor_evt(
+            &self,
+            _this_actor: ActorRef<Self::Msg>,
+            _message: SupervisionEvent,
+            _state: &mut Self::State,
+        ) -> Result<(), ActorProcessingErr> {
+            panic!("Batching supervisors shouldn't receive individual events");
+        }
+
+        async fn handle_supervisor_evt_batch(
+            &self,
+            _this_actor: ActorRef<Self::Msg>,
+            messages: Vec<SupervisionEvent>,
+            _state: &mut Self::State,
+        ) -> Result<(), ActorProcessingErr> {
+            let failures = messages
+                .iter()
+                .filter(|evt| matches!(evt, SupervisionEvent::ActorFailed(_, _)))
+                .count();
+            if failures > 0 {
+                self.batches.lock().unwrap().push(failures);
+            }
+            Ok(())
+        }
+    }
+
+    let batches = Arc::new(std::sync::Mutex::new(vec![]));
+    let (supervisor_ref, s_handle) = Actor::spawn(
+        None,
+        Supervisor {
+            batches: batches.clone(),
+        },
+        (),
+    )
+    .await
+    .expect("Supervisor panicked on startup");
+
+    let mut children = vec![];
+    for _ in 0..10 {
+        let (child, handle) = Actor::spawn_linked(None, Child, (), supervisor_ref.get_cell())
+            .await
+            .expect("Child panicked on startup");
+        children.push((child, handle));
+    }
+
+    // let the batch of startup events flush
+    crate::concurrency::sleep(Duration::from_millis(200)).await;
+
+    // crash all the children at once
+    for (child, _) in children.iter() {
+        child.cast(()).expect("Failed to send to child");
+    }
+    for (_, handle) in children {
+        handle.await.unwrap();
+    }
+
+    periodic_check(
+        || batches.lock().unwrap().iter().sum::<usize>() == 10,
+        Duration::from_millis(1000),
+    )
+    .await;
+    // the crash storm is delivered as a single batch
+    assert_eq!(vec![10], *batches.lock().unwrap());
+
+    supervisor_ref.stop(None);
+    s_handle.await.unwrap();
+}
+
+#[crate::concurrency::test]
+#[tracing_test::traced_test]
+async fn test_stop_closes_supervision_batch_window() {
+    struct Child;
+
+    #[cfg_attr(feature = "async-trait", crate::async_trait)]
+    impl Actor for Child {
+        type Msg = ();
+        type State = ();
+        type Arguments = ();
+        async fn pre_start(
+            &self,
+            _this_actor: ActorRef<Self::Msg>,
+            _: (),
+        ) -> Result<Self::State, ActorProcessingErr> {
+            Ok(())
+        }
+        async fn handle(
+            &self,
+            _this_actor: ActorRef<Self::Msg>,
+            _message: Self::Msg,
+            _state: &mut Self::State,
+        ) -> Result<(), ActorProcessingErr> {
+            Err(From::from("boom"))
+        }
+    }
+
+    struct Supervisor {
+        // the number of events delivered in each batch
+        batches: Arc<std::sync::Mutex<Vec<usize>>>,
+    }
+
+    #[cfg_attr(feature = "async-trait", crate::async_trait)]
+    impl Actor for Supervisor {
+        type Msg = ();
+        type State = ();
+        type Arguments = ();
+        async fn pre_start(
+            &self,
+            _this_actor: ActorRef<Self::Msg>,
+            _: (),
+        ) -> Result<Self::State, ActorProcessingErr> {
+            Ok(())
+        }
+
+        fn supervision_batch_window(&self) -> Option<Duration> {
+            Some(Duration::from_secs(10))
+        }
+
+        async fn handle_supervisor_evt_batch(
+            &self,
+            _this_actor: ActorRef<Self::Msg>,
+            messages: Vec<SupervisionEvent>,
+            _state: &mut Self::State,
+        ) -> Result<(), ActorProcessingErr> {
+            self.batches.lock().unwrap().push(messages.len());
+            Ok(())
+        }
+    }
+
+    let batches = Arc::new(std::sync::Mutex::new(vec![]));
+    let (supervisor_ref, s_handle) = Actor::spawn(
+        None,
+        Supervisor {
+            batches: batches.clone(),
+        },
+        (),
+    )
+    .await
+    .expect("Supervisor panicked on startup");
+
+    // the child's startup event opens the batch window
+    let (child, c_handle) = Actor::spawn_linked(None, Child, (), supervisor_ref.get_cell())
+        .await
+        .expect("Child panicked on startup");
+    child.cast(()).expect("Failed to send to child");
+    c_handle.await.unwrap();
+
+    // the stop request doesn't wait for the window to elapse
+    let started = crate::concurrency::Instant::now();
+    supervisor_ref
+        .stop_and_wait(None, Some(Duration::from_secs(1)))
+        .await
+        .expect("Supervisor didn't stop within the batch window");
+    assert!(started.elapsed() < Duration::from_secs(1));
+    s_handle.await.unwrap();
+
+    // the events collected so far were still handled before stopping
+    assert_eq!(vec![2], *batches.lock().unwrap());
+}
+
+#[crate::concurrency::test]
+#[tracing_test::traced_test]
+async fn test_structured_stop_reasons() {
+    use crate::StopReason;
+
+    struct Child;
+
+    #[cfg_attr(feature = "async-trait", crate::async_trait)]
+    impl Actor for Child {
+        type Msg = ();
+        type State = ();
+        type Arguments = ();
+        async fn pre_start(
+            &self,
+            _this_actor: ActorRef<Self::Msg>,
+            _: (),
+        ) -> Result<Self::State, ActorProcessingErr> {
+            Ok(())
+        }
+    }
+
+    struct Supervisor {
+        reasons: Arc<std::sync::Mutex<Vec<String>>>,
+    }
+
+    #[cfg_attr(feature = "async-trait", crate::async_trait)]
+    impl Actor for Supervisor {
+        type Msg = ();
+        type State = ();
+        type Arguments = ();
+        async fn pre_start(
+            &self,
+            _this_actor: ActorRef<Self::Msg>,
+            _: (),
+        ) -> Result<Self::State, ActorProcessingErr> {
+            Ok(())
+        }
+
+        async fn handle_supervisor_evt(
+            &self,
+            _this_actor: ActorRef<Self::Msg>,
+            message: SupervisionEvent,
+            _state: &mut Self::State,
+        ) -> Result<(), ActorProcessingErr> {
+            if let SupervisionEvent::ActorTerminated(_, _, reason) = message {
+                let tag = match reason {
+                    None => "none".to_string(),
+                    Some(StopReason::Custom(reason)) => format!("custom:{reason}"),
+                    Some(StopReason::Shutdown(Some(context))) => format!("shutdown:{context}"),
+                    Some(StopReason::Killed) => "killed".to_string(),
+                    Some(StopReason::Drained) => "drained".to_string(),
+                    Some(StopReason::Error(err)) => format!("error:{err}"),
+                    Some(StopReason::Supervisor) => "supervisor".to_string(),
+                    Some(StopReason::Normal) => "normal".to_string(),
+                    Some(other) => format!("other:{other}"),
+                };
+                self.reasons.lock().unwrap().push(tag);
+            }
+            Ok(())
+        }
+    }
+
+    let reasons = Arc::new(std::sync::Mutex::new(vec![]));
+    let (supervisor_ref, s_handle) = Actor::spawn(
+        None,
+        Supervisor {
+            reasons: reasons.clone(),
+        },
+        (),
+    )
+    .await
+    .expect("Supervisor panicked on startup");
+
+    let expected = [
+        "none",
+        "custom:bye",
+        "shutdown:Exit after 10ms",
+        "killed",
+        "drained",
+        "error:boom",
+        "none",
+        "supervisor",
+        "normal",
+    ];
+    for (i, want) in expected.iter().enumerate() {
+        let (child, c_handle) = Actor::spawn_linked(None, Child, (), supervisor_ref.get_cell())
+            .await
+            .expect("Child panicked on startup");
+        match i {
+            0 => child.stop(None),
+            1 => child.stop(Some("bye".into())),
+            2 => {
+                crate::time::exit_after(Duration::from_millis(10), child.get_cell());
+            }
+            3 => child.kill(),
+            4 => child.drain().expect("Failed to drain child"),
+            5 => {
+                let err: ActorProcessingErr = From::from("boom");
+                child.stop_with_reason(Some(err.into()));
+            }
+            6 => supervisor_ref.stop_children(None),
+            7 => supervisor_ref.stop_children_with_reason(Some(StopReason::Supervisor)),
+            _ => child.stop_with_reason(Some(StopReason::Normal)),
+        }
+        c_handle.await.unwrap();
+        periodic_check(
+            || reasons.lock().unwrap().len() == i + 1,
+            Duration::from_millis(500),
+        )
+        .await;
+        assert_eq!(*want, reasons.lock().unwrap()[i]);
+    }
+
+    supervisor_ref.stop(None);
+    s_handle.await.unwrap();
+}
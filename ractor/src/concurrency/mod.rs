@@ -38,6 +38,11 @@ pub type BroadcastSender<T> = tokio::sync::broadcast::Sender<T>;
 /// A bounded broadcast receiver
 pub type BroadcastReceiver<T> = tokio::sync::broadcast::Receiver<T>;
 
+/// A single-value, latest-wins, watch sender
+pub type WatchSender<T> = tokio::sync::watch::Sender<T>;
+/// A single-value, latest-wins, watch receiver
+pub type WatchReceiver<T> = tokio::sync::watch::Receiver<T>;
+
 /// MPSC bounded channel
 pub fn mpsc_bounded<T>(buffer: usize) -> (MpscSender<T>, MpscReceiver<T>) {
     tokio::sync::mpsc::channel(buffer)
@@ -58,6 +63,11 @@ pub fn broadcast<T: Clone>(buffer: usize) -> (BroadcastSender<T>, BroadcastRecei
     tokio::sync::broadcast::channel(buffer)
 }
 
+/// Watch channel, initialized with the provided value
+pub fn watch<T>(init: T) -> (WatchSender<T>, WatchReceiver<T>) {
+    tokio::sync::watch::channel(init)
+}
+
 #[cfg(not(feature = "async-std"))]
 pub mod tokio_primitives;
 #[cfg(not(feature = "async-std"))]
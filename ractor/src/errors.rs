@@ -13,7 +13,11 @@ use crate::ActorName;
 pub type ActorProcessingErr = Box<dyn std::error::Error + Send + Sync + 'static>;
 
 /// Spawn errors starting an actor
+///
+/// Limits on spawning, like [SpawnErr::ActorLimitReached], add ways for a spawn to fail
+/// over time. Code matching on a [SpawnErr] must handle unknown errors with a catch-all arm.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum SpawnErr {
     /// Actor panic'd or returned an error during startup
     StartupFailed(ActorProcessingErr),
@@ -21,6 +25,9 @@ pub enum SpawnErr {
     ActorAlreadyStarted,
     /// The named actor is already registered in the registry
     ActorAlreadyRegistered(ActorName),
+    /// The process-wide cap on live actors (see [crate::set_max_actors]) has been reached.
+    /// Holds the cap which was in effect when the spawn was refused
+    ActorLimitReached(usize),
 }
 
 impl std::error::Error for SpawnErr {
@@ -51,6 +58,9 @@ impl Display for SpawnErr {
                     "Actor '{actor_name}' is already registered in the actor registry"
                 )
             }
+            Self::ActorLimitReached(max) => {
+                write!(f, "The limit of {max} live actors has been reached")
+            }
         }
     }
 }
@@ -101,6 +111,9 @@ impl Display for ActorErr {
 }
 
 /// A messaging error has occurred
+///
+/// New variants may be added in future releases, so matches need a wildcard arm
+#[non_exhaustive]
 pub enum MessagingErr<T> {
     /// The channel you're trying to send a message too has been dropped/closed.
     /// If you're sending to an [crate::ActorCell] then that means the actor has died
@@ -118,6 +131,18 @@ pub enum MessagingErr<T> {
     /// This happens if you have an [crate::ActorCell] which has the type id of its
     /// handler and you try to use an alternate handler to send a message
     InvalidActorType,
+
+    /// The message would push the actor's mailbox over its byte budget
+    /// (see [crate::Actor::max_total_mailbox_bytes]), so it was rejected
+    /// before being enqueued.
+    ///
+    /// Includes the message which was rejected so the caller can retry later or
+    /// perform another operation with the message.
+    MailboxBudgetExceeded(T),
+
+    /// Installing the redirect would make the actor (eventually) redirect to itself
+    /// (see [crate::ActorCell::redirect_to])
+    RedirectCycle,
 }
 
 impl<T> MessagingErr<T> {
@@ -131,6 +156,10 @@ impl<T> MessagingErr<T> {
             MessagingErr::SendErr(err) => MessagingErr::SendErr(mapper(err)),
             MessagingErr::ChannelClosed => MessagingErr::ChannelClosed,
             MessagingErr::InvalidActorType => MessagingErr::InvalidActorType,
+            MessagingErr::MailboxBudgetExceeded(msg) => {
+                MessagingErr::MailboxBudgetExceeded(mapper(msg))
+            }
+            MessagingErr::RedirectCycle => MessagingErr::RedirectCycle,
         }
     }
 }
@@ -143,6 +172,8 @@ impl<T> std::fmt::Debug for MessagingErr<T> {
             Self::SendErr(_) => write!(f, "SendErr"),
             Self::ChannelClosed => write!(f, "RecvErr"),
             Self::InvalidActorType => write!(f, "InvalidActorType"),
+            Self::MailboxBudgetExceeded(_) => write!(f, "MailboxBudgetExceeded"),
+            Self::RedirectCycle => write!(f, "RedirectCycle"),
         }
     }
 }
@@ -177,6 +208,15 @@ impl<T> Display for MessagingErr<T> {
             Self::SendErr(_) => {
                 write!(f, "Messaging failed to enqueue the message to the specified actor, the actor is likely terminated")
             }
+            Self::MailboxBudgetExceeded(_) => {
+                write!(f, "Messaging failed because the message would exceed the actor's mailbox byte budget")
+            }
+            Self::RedirectCycle => {
+                write!(
+                    f,
+                    "Messaging failed because the redirect would form a cycle"
+                )
+            }
         }
     }
 }
@@ -200,7 +240,10 @@ impl<T> RactorErr<T> {
     ///
     /// Returns [true] if the error contains a message payload of type `T`, [false] otherwise.
     pub fn has_message(&self) -> bool {
-        matches!(self, Self::Messaging(MessagingErr::SendErr(_)))
+        matches!(
+            self,
+            Self::Messaging(MessagingErr::SendErr(_) | MessagingErr::MailboxBudgetExceeded(_))
+        )
     }
     /// Try and extract the message payload from the contained error. This consumes the
     /// [RactorErr] instance in order to not have require cloning the message payload.
@@ -208,7 +251,10 @@ impl<T> RactorErr<T> {
     ///
     /// Returns [Some(`T`)] if there is a message payload, [None] otherwise.
     pub fn try_get_message(self) -> Option<T> {
-        if let Self::Messaging(MessagingErr::SendErr(msg)) = self {
+        if let Self::Messaging(
+            MessagingErr::SendErr(msg) | MessagingErr::MailboxBudgetExceeded(msg),
+        ) = self
+        {
             Some(msg)
         } else {
             None
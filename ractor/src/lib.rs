@@ -171,9 +171,12 @@ pub mod message;
 pub mod pg;
 pub mod port;
 pub mod registry;
+pub mod router;
 pub mod rpc;
 #[cfg(feature = "cluster")]
 pub mod serialization;
+#[cfg(any(test, feature = "testkit"))]
+pub mod testkit;
 pub mod time;
 
 #[cfg(not(feature = "async-trait"))]
@@ -196,16 +199,20 @@ use tracing_subscriber as _;
 
 // ======================== Re-exports ======================== //
 
-pub use actor::actor_cell::{ActorCell, ActorStatus, ACTIVE_STATES};
+pub use actor::actor_cell::{ActorCell, ActorStatus, ShutdownOutcome, ACTIVE_STATES};
+pub use actor::actor_count::{current_actor_count, set_max_actors};
 pub use actor::actor_id::ActorId;
-pub use actor::actor_ref::ActorRef;
-pub use actor::messages::{Signal, SupervisionEvent};
+pub use actor::actor_ref::{ActorRef, ActorSender};
+#[cfg(feature = "latency_histogram")]
+pub use actor::latency::LatencySnapshot;
+pub use actor::layer::{HandlerLayer, Layered, NextHandler};
+pub use actor::messages::{Signal, StopReason, SupervisionEvent};
 pub use actor::{Actor, ActorRuntime};
 #[cfg(feature = "async-trait")]
 pub use async_trait::async_trait;
 pub use errors::{ActorErr, ActorProcessingErr, MessagingErr, RactorErr, SpawnErr};
 pub use message::Message;
-pub use port::{OutputMessage, OutputPort, RpcReplyPort};
+pub use port::{broadcast_shared, OutputMessage, OutputPort, RpcReplyPort, SnapshotPort};
 #[cfg(feature = "cluster")]
 pub use serialization::BytesConvertable;
 
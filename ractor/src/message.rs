@@ -65,6 +65,9 @@ pub struct BoxedMessage {
     #[cfg(feature = "cluster")]
     pub serialized_msg: Option<SerializedMessage>,
     pub(crate) span: Option<tracing::Span>,
+    /// The weight reserved in the receiving mailbox's byte budget for this message, which
+    /// is released when the message is dequeued
+    pub(crate) weight: usize,
 }
 
 impl std::fmt::Debug for BoxedMessage {
@@ -140,12 +143,14 @@ pub trait Message: Any + Send + Sized + 'static {
                 msg: None,
                 serialized_msg: Some(self.serialize()?),
                 span: None,
+                weight: 0,
             })
         } else if pid.is_local() {
             Ok(BoxedMessage {
                 msg: Some(Box::new(self)),
                 serialized_msg: None,
                 span: Some(tracing::Span::current()),
+                weight: 0,
             })
         } else {
             Err(BoxedDowncastErr)
@@ -159,6 +164,7 @@ pub trait Message: Any + Send + Sized + 'static {
         Ok(BoxedMessage {
             msg: Some(Box::new(self)),
             span: Some(tracing::Span::current()),
+            weight: 0,
         })
     }
 
@@ -18,6 +18,10 @@ use crate::MessagingErr;
 pub mod output;
 pub use output::*;
 
+// ============ Shared snapshot ports ============ //
+pub mod snapshot;
+pub use snapshot::*;
+
 // ============ Rpc (one-use) Ports ============ //
 
 /// A remote procedure call's reply port. Wrapper of [concurrency::OneshotSender] with a
@@ -53,6 +57,15 @@ impl<TMsg> RpcReplyPort<TMsg> {
     pub fn is_closed(&self) -> bool {
         self.port.is_closed()
     }
+
+    /// Wait for the caller to stop waiting on the reply, i.e. the call was cancelled
+    /// (see [crate::rpc::CallHandle::cancel]), timed out, or the caller was dropped.
+    ///
+    /// Handlers doing expensive work for a call can race their work against this to
+    /// short-circuit once nobody is waiting for the reply anymore.
+    pub async fn cancelled(&mut self) {
+        self.port.closed().await
+    }
 }
 
 impl<TMsg> From<concurrency::OneshotSender<TMsg>> for RpcReplyPort<TMsg> {
@@ -0,0 +1,243 @@
+// Copyright (c) Sean Lawlor
+//
+// This source code is licensed under both the MIT license found in the
+// LICENSE-MIT file in the root directory of this source tree.
+
+//! Tests on shared snapshot broadcasting
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use crate::common_test::periodic_check;
+use crate::concurrency::Duration;
+use crate::{Actor, ActorProcessingErr, ActorRef};
+
+use super::*;
+
+struct Config {
+    version: u64,
+    #[allow(dead_code)]
+    payload: Vec<u8>,
+}
+
+struct ConfigUpdate(Arc<Config>);
+#[cfg(feature = "cluster")]
+impl crate::Message for ConfigUpdate {}
+
+impl From<Arc<Config>> for ConfigUpdate {
+    fn from(value: Arc<Config>) -> Self {
+        Self(value)
+    }
+}
+
+struct ConfigActor {
+    latest_version: Arc<AtomicU64>,
+    received: Arc<AtomicU64>,
+}
+
+#[cfg_attr(feature = "async-trait", crate::async_trait)]
+impl Actor for ConfigActor {
+    type Msg = ConfigUpdate;
+    type Arguments = ();
+    type State = Option<Arc<Config>>;
+
+    async fn pre_start(
+        &self,
+        _myself: ActorRef<Self::Msg>,
+        _: (),
+    ) -> Result<Self::State, ActorProcessingErr> {
+        Ok(None)
+    }
+
+    async fn handle(
+        &self,
+        _myself: ActorRef<Self::Msg>,
+        ConfigUpdate(config): Self::Msg,
+        state: &mut Self::State,
+    ) -> Result<(), ActorProcessingErr> {
+        self.latest_version.store(config.version, Ordering::SeqCst);
+        self.received.fetch_add(1, Ordering::SeqCst);
+        *state = Some(config);
+        Ok(())
+    }
+}
+
+#[crate::concurrency::test]
+#[tracing_test::traced_test]
+async fn test_broadcast_shared_does_not_clone() {
+    let latest = Arc::new(AtomicU64::new(0));
+    let received = Arc::new(AtomicU64::new(0));
+
+    let mut actors = vec![];
+    let mut handles = vec![];
+    for _ in 0..5 {
+        let (actor, handle) = Actor::spawn(
+            None,
+            ConfigActor {
+                latest_version: latest.clone(),
+                received: received.clone(),
+            },
+            (),
+        )
+        .await
+        .expect("Failed to spawn test actor");
+        actors.push(actor);
+        handles.push(handle);
+    }
+
+    let snapshot = Arc::new(Config {
+        version: 1,
+        payload: vec![0u8; 1024 * 1024],
+    });
+    let sent = broadcast_shared(&actors, snapshot.clone());
+    assert_eq!(5, sent);
+
+    periodic_check(
+        || received.load(Ordering::SeqCst) == 5,
+        Duration::from_secs(1),
+    )
+    .await;
+    // every actor holds a reference to the same allocation, plus our own
+    assert_eq!(6, Arc::strong_count(&snapshot));
+
+    // sends to stopped actors are skipped
+    actors[0].stop(None);
+    handles.remove(0).await.unwrap();
+    let sent = broadcast_shared(&actors, snapshot.clone());
+    assert_eq!(4, sent);
+
+    for actor in actors {
+        actor.stop(None);
+    }
+    for handle in handles {
+        handle.await.unwrap();
+    }
+}
+
+#[crate::concurrency::test]
+#[tracing_test::traced_test]
+async fn test_snapshot_port_subscribe_receives_latest() {
+    let latest = Arc::new(AtomicU64::new(0));
+    let received = Arc::new(AtomicU64::new(0));
+
+    let port = SnapshotPort::new(Config {
+        version: 1,
+        payload: vec![],
+    });
+
+    let (actor, handle) = Actor::spawn(
+        None,
+        ConfigActor {
+            latest_version: latest.clone(),
+            received: received.clone(),
+        },
+        (),
+    )
+    .await
+    .expect("Failed to spawn test actor");
+
+    port.subscribe(actor.clone());
+
+    // the current snapshot is delivered upon subscription
+    periodic_check(
+        || latest.load(Ordering::SeqCst) == 1,
+        Duration::from_secs(1),
+    )
+    .await;
+
+    for version in 2..=10 {
+        port.publish(Config {
+            version,
+            payload: vec![],
+        });
+    }
+
+    // the subscriber converges on the latest snapshot, and intermediate
+    // snapshots may be coalesced away
+    periodic_check(
+        || latest.load(Ordering::SeqCst) == 10,
+        Duration::from_secs(1),
+    )
+    .await;
+    assert!(received.load(Ordering::SeqCst) <= 10);
+    assert_eq!(10, port.latest().version);
+
+    actor.stop(None);
+    handle.await.unwrap();
+}
+
+#[crate::concurrency::test]
+#[tracing_test::traced_test]
+async fn test_snapshot_receiver() {
+    let port = SnapshotPort::new(Config {
+        version: 1,
+        payload: vec![],
+    });
+    let mut rx = port.watch();
+
+    assert!(!rx.has_changed());
+    assert_eq!(1, rx.latest().version);
+
+    let shared = Arc::new(Config {
+        version: 2,
+        payload: vec![],
+    });
+    port.publish_shared(shared.clone());
+    assert!(rx.has_changed());
+    let seen = rx.latest();
+    assert!(Arc::ptr_eq(&shared, &seen));
+    assert!(!rx.has_changed());
+
+    let waiter = crate::concurrency::spawn(async move { rx.changed().await.map(|c| c.version) });
+    port.publish(Config {
+        version: 3,
+        payload: vec![],
+    });
+    assert_eq!(Some(3), waiter.await.unwrap());
+
+    // once the port is dropped, waiting for a change ends
+    let mut rx = port.watch();
+    drop(port);
+    assert!(rx.changed().await.is_none());
+}
+
+#[crate::concurrency::test]
+#[tracing_test::traced_test]
+async fn test_snapshot_subscription_ends_with_actor() {
+    let port = SnapshotPort::new(Config {
+        version: 1,
+        payload: vec![],
+    });
+    let received = Arc::new(AtomicU64::new(0));
+    let (actor, handle) = Actor::spawn(
+        None,
+        ConfigActor {
+            latest_version: Arc::new(AtomicU64::new(0)),
+            received: received.clone(),
+        },
+        (),
+    )
+    .await
+    .expect("Failed to start actor");
+    port.subscribe(actor.clone());
+    periodic_check(
+        || received.load(Ordering::SeqCst) == 1,
+        Duration::from_millis(500),
+    )
+    .await;
+
+    // the subscription ends once the actor stops, without another publish
+    actor.stop(None);
+    handle.await.unwrap();
+    periodic_check(
+        || {
+            port.subscriptions
+                .read()
+                .unwrap()
+                .iter()
+                .all(|sub| sub.is_finished())
+        },
+        Duration::from_millis(500),
+    )
+    .await;
+}
@@ -84,7 +84,8 @@ use dashmap::mapref::entry::Entry::{Occupied, Vacant};
 use dashmap::DashMap;
 use once_cell::sync::OnceCell;
 
-use crate::{ActorCell, ActorName};
+use crate::concurrency::Duration;
+use crate::{ActorCell, ActorId, ActorName, MessagingErr, RactorErr, ShutdownOutcome};
 
 #[cfg(feature = "cluster")]
 pub mod pid_registry;
@@ -120,10 +121,30 @@ pub(crate) fn register(name: ActorName, actor: ActorCell) -> Result<(), ActorReg
     }
 }
 
-/// Remove an actor from the registry given it's actor name
-pub(crate) fn unregister(name: ActorName) {
+/// Remove an actor from the registry given it's actor name, if the name is still
+/// held by the actor with the provided id (the name may have been handed over to
+/// another actor, e.g. by [ActorCell::hot_restart])
+pub(crate) fn unregister(name: ActorName, id: ActorId) {
     if let Some(reg) = ACTOR_REGISTRY.get() {
-        let _ = reg.remove(&name);
+        let _ = reg.remove_if(&name, |_, cell| cell.get_id() == id);
+    }
+}
+
+/// Hand a name over from the actor with the id `from` to the actor `to` in a single
+/// registry update, as long as `from` still holds the name or it has since been released
+///
+/// Returns [true] if `to` holds the name afterwards, [false] if another actor holds it
+pub(crate) fn hand_over(name: ActorName, from: ActorId, to: ActorCell) -> bool {
+    match get_actor_registry().entry(name) {
+        Occupied(mut entry) if entry.get().get_id() == from => {
+            entry.insert(to);
+            true
+        }
+        Occupied(_) => false,
+        Vacant(vacancy) => {
+            vacancy.insert(to);
+            true
+        }
     }
 }
 
@@ -146,3 +167,104 @@ pub fn registered() -> Vec<ActorName> {
     let reg = get_actor_registry();
     reg.iter().map(|kvp| kvp.key().clone()).collect::<Vec<_>>()
 }
+
+/// The outcome of a single phase of [shutdown_phased]
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct PhaseShutdownReport {
+    /// The actors which drained their message queue and stopped within the phase's timeout
+    pub drained: Vec<ActorId>,
+    /// The actors which didn't drain within the phase's timeout and were force-killed
+    pub killed: Vec<ActorId>,
+    /// The actors which had already stopped by the time their phase started, e.g. because
+    /// their supervisor was stopped in an earlier phase
+    pub already_stopped: Vec<ActorId>,
+    /// The actors which couldn't be shut down, along with the error
+    pub failed: Vec<(ActorId, String)>,
+}
+
+/// The outcome of a [shutdown_phased] call, with one entry per phase in the order
+/// the phases were shut down
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct PhasedShutdownReport {
+    /// The per-phase outcomes
+    pub phases: Vec<PhaseShutdownReport>,
+}
+
+impl PhasedShutdownReport {
+    /// Retrieve all the actors which had to be force-killed, along with the index of the
+    /// phase they were in
+    pub fn killed(&self) -> Vec<(usize, ActorId)> {
+        self.phases
+            .iter()
+            .enumerate()
+            .flat_map(|(phase, report)| report.killed.iter().map(move |id| (phase, *id)))
+            .collect()
+    }
+
+    /// Retrieve all the actors which couldn't be shut down, along with the index of the
+    /// phase they were in and the error
+    pub fn failed(&self) -> Vec<(usize, ActorId, String)> {
+        self.phases
+            .iter()
+            .enumerate()
+            .flat_map(|(phase, report)| {
+                report
+                    .failed
+                    .iter()
+                    .map(move |(id, err)| (phase, *id, err.clone()))
+            })
+            .collect()
+    }
+
+    /// Returns [true] if every actor drained gracefully (or had already stopped), [false]
+    /// if any had to be killed or couldn't be shut down
+    pub fn is_graceful(&self) -> bool {
+        self.phases
+            .iter()
+            .all(|phase| phase.killed.is_empty() && phase.failed.is_empty())
+    }
+}
+
+/// Shutdown a set of actors in ordered phases. Each phase is drained (see
+/// [ActorCell::stop_or_kill]) concurrently, and the next phase starts only once every
+/// actor in the current phase has exited. Actors which don't finish draining within
+/// the phase's timeout are killed.
+///
+/// Unlike shutdown ordering via the supervision tree, phases can cut across the supervision
+/// hierarchy, e.g. stopping all HTTP listeners, then all workers, and finally the database
+/// connection. However an actor which stops still terminates its children, so a child
+/// scheduled for the same or a later phase than its supervisor doesn't get to drain, and
+/// is reported as already stopped in its phase. Schedule children in an earlier phase than
+/// their supervisor for them to drain gracefully.
+///
+/// * `phases` - The actors to shut down, grouped by phase in shutdown order
+/// * `per_phase_timeout` - The amount of time each phase's actors have to drain before being killed
+///
+/// Returns a [PhasedShutdownReport] denoting how each actor in each phase was shut down
+pub async fn shutdown_phased(
+    phases: Vec<Vec<ActorCell>>,
+    per_phase_timeout: Duration,
+) -> PhasedShutdownReport {
+    let mut report = PhasedShutdownReport::default();
+    for phase in phases {
+        let outcomes = futures::future::join_all(phase.iter().map(|actor| async move {
+            (actor.get_id(), actor.stop_or_kill(per_phase_timeout).await)
+        }))
+        .await;
+
+        let mut phase_report = PhaseShutdownReport::default();
+        for (id, outcome) in outcomes {
+            match outcome {
+                Ok(ShutdownOutcome::Drained) => phase_report.drained.push(id),
+                Ok(ShutdownOutcome::Killed) => phase_report.killed.push(id),
+                // the drain couldn't be sent, as the actor's mailbox is closed
+                Err(RactorErr::Messaging(
+                    MessagingErr::SendErr(()) | MessagingErr::ChannelClosed,
+                )) => phase_report.already_stopped.push(id),
+                Err(err) => phase_report.failed.push((id, err.to_string())),
+            }
+        }
+        report.phases.push(phase_report);
+    }
+    report
+}
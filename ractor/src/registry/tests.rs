@@ -388,3 +388,130 @@ mod pid_registry_tests {
         tracing::debug!("{:?}", PidLifecycleEvent::Terminate(test_actor.get_cell()));
     }
 }
+
+#[crate::concurrency::test]
+#[tracing_test::traced_test]
+async fn test_phased_shutdown() {
+    use std::sync::{Arc, Mutex};
+
+    use crate::concurrency::sleep;
+
+    struct PhaseActor {
+        phase: usize,
+        stopped: Arc<Mutex<Vec<usize>>>,
+    }
+
+    #[cfg_attr(feature = "async-trait", crate::async_trait)]
+    impl Actor for PhaseActor {
+        type Msg = u64;
+        type Arguments = ();
+        type State = ();
+
+        async fn pre_start(
+            &self,
+            _this_actor: crate::ActorRef<Self::Msg>,
+            _: (),
+        ) -> Result<Self::State, ActorProcessingErr> {
+            Ok(())
+        }
+
+        async fn handle(
+            &self,
+            _this_actor: crate::ActorRef<Self::Msg>,
+            delay_ms: Self::Msg,
+            _: &mut Self::State,
+        ) -> Result<(), ActorProcessingErr> {
+            sleep(Duration::from_millis(delay_ms)).await;
+            Ok(())
+        }
+
+        async fn post_stop(
+            &self,
+            _this_actor: crate::ActorRef<Self::Msg>,
+            _: &mut Self::State,
+        ) -> Result<(), ActorProcessingErr> {
+            self.stopped.lock().unwrap().push(self.phase);
+            Ok(())
+        }
+    }
+
+    let stopped = Arc::new(Mutex::new(vec![]));
+    let mut phases = vec![];
+    let mut actors = vec![];
+    let mut handles = vec![];
+    for phase in 0..3 {
+        let mut cells = vec![];
+        for _ in 0..2 {
+            let (actor, handle) = Actor::spawn(
+                None,
+                PhaseActor {
+                    phase,
+                    stopped: stopped.clone(),
+                },
+                (),
+            )
+            .await
+            .expect("Actor failed to start");
+            cells.push(actor.get_cell());
+            actors.push(actor);
+            handles.push(handle);
+        }
+        phases.push(cells);
+    }
+
+    // a supervisor in phase 0 terminates its child in phase 2 when it stops
+    let (supervisor, s_handle) = Actor::spawn(
+        None,
+        PhaseActor {
+            phase: 0,
+            stopped: stopped.clone(),
+        },
+        (),
+    )
+    .await
+    .expect("Actor failed to start");
+    let (child, c_handle) = Actor::spawn_linked(
+        None,
+        PhaseActor {
+            phase: 2,
+            stopped: stopped.clone(),
+        },
+        (),
+        supervisor.get_cell(),
+    )
+    .await
+    .expect("Actor failed to start");
+    phases[0].push(supervisor.get_cell());
+    phases[2].push(child.get_cell());
+    handles.extend([s_handle, c_handle]);
+
+    // the first actor of phase 1 is stuck on a long message, and won't drain in time
+    let stuck = actors[2].clone();
+    stuck.cast(10_000).expect("Failed to send message");
+    // the second actor of phase 2 has a short backlog, which drains in time
+    actors[5].cast(10).expect("Failed to send message");
+
+    // an already stopped actor is reported as such
+    actors[0].stop(None);
+    handles.remove(0).await.unwrap();
+    stopped.lock().unwrap().clear();
+
+    let report = crate::registry::shutdown_phased(phases, Duration::from_millis(200)).await;
+    for handle in handles {
+        handle.await.unwrap();
+    }
+
+    assert_eq!(3, report.phases.len());
+    assert_eq!(2, report.phases[0].drained.len());
+    assert!(report.phases[0].killed.is_empty());
+    assert_eq!(vec![actors[0].get_id()], report.phases[0].already_stopped);
+    assert_eq!(vec![actors[3].get_id()], report.phases[1].drained);
+    assert_eq!(vec![(1, stuck.get_id())], report.killed());
+    assert_eq!(2, report.phases[2].drained.len());
+    assert_eq!(vec![child.get_id()], report.phases[2].already_stopped);
+    assert!(report.failed().is_empty());
+    assert!(!report.is_graceful());
+
+    // phases stopped in order. The killed actors don't run post_stop
+    assert_eq!(vec![0, 0, 1, 2, 2], *stopped.lock().unwrap());
+}
@@ -0,0 +1,222 @@
+// Copyright (c) Sean Lawlor
+//
+// This source code is licensed under both the MIT license found in the
+// LICENSE-MIT file in the root directory of this source tree.
+
+//! Content-based routing of messages to actors
+//!
+//! A [Router] holds an ordered list of routes, each mapping a predicate over the message to
+//! a target actor, along with an optional fallback for messages no route matched. A
+//! dispatcher actor can hold a [Router] in its state and forward its messages with a single
+//! call, rather than a large `match` in its handler.
+//!
+//! ```rust
+//! use ractor::router::Router;
+//! use ractor::ActorRef;
+//!
+//! enum Request {
+//!     Read(String),
+//!     Write(String, Vec<u8>),
+//! }
+//! # #[cfg(feature = "cluster")]
+//! # impl ractor::Message for Request {}
+//!
+//! fn build_router(
+//!     readers: ActorRef<Request>,
+//!     writers: ActorRef<Request>,
+//!     unhandled: ActorRef<Request>,
+//! ) -> Router<Request> {
+//!     let mut router = Router::new();
+//!     router
+//!         .add_route(|req| matches!(req, Request::Read(_)), readers)
+//!         .add_route(|req| matches!(req, Request::Write(..)), writers)
+//!         .set_fallback(Some(unhandled));
+//!     router
+//! }
+//! ```
+//!
+//! ## Ordering
+//!
+//! Routes are evaluated in the order they were added. [Router::route] forwards the message
+//! to the first matching route only, while [Router::route_all] forwards a copy to every
+//! matching route. The fallback only receives messages which no route accepted.
+//!
+//! ## Dead targets
+//!
+//! A target which defers its readiness (see [crate::Actor::defer_readiness]) and hasn't
+//! marked itself ready yet, or fails to accept the message (because it has stopped, or its
+//! mailbox is over budget) is skipped. Routing falls through to the next matching route and
+//! then the fallback, as if the skipped route didn't match. The fallback is skipped the same
+//! way. Targets which don't defer their readiness are routed to while still starting, since
+//! messages queue in their mailbox until they're running. An error is only returned when
+//! neither a matching route nor the fallback accepted the message, in which case the message
+//! is handed back in the error. Routes to stopped actors stay in the router until removed
+//! with [Router::remove_stopped].
+
+use crate::{ActorId, ActorRef, Message, MessagingErr, ACTIVE_STATES};
+
+#[cfg(test)]
+mod tests;
+
+/// A predicate selecting the messages for a route
+type RoutePredicate<TMsg> = Box<dyn Fn(&TMsg) -> bool + Send + Sync>;
+
+/// A single route of a [Router]
+struct Route<TMsg> {
+    predicate: RoutePredicate<TMsg>,
+    target: ActorRef<TMsg>,
+}
+
+/// Routes messages to actors based on their content. See the [module docs](self) for
+/// the ordering of routes and how stopped targets are handled.
+pub struct Router<TMsg>
+where
+    TMsg: Message,
+{
+    routes: Vec<Route<TMsg>>,
+    fallback: Option<ActorRef<TMsg>>,
+}
+
+impl<TMsg> std::fmt::Debug for Router<TMsg>
+where
+    TMsg: Message,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Router")
+            .field(
+                "routes",
+                &self
+                    .routes
+                    .iter()
+                    .map(|route| route.target.get_id())
+                    .collect::<Vec<_>>(),
+            )
+            .field("fallback", &self.fallback)
+            .finish()
+    }
+}
+
+impl<TMsg> Default for Router<TMsg>
+where
+    TMsg: Message,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<TMsg> Router<TMsg>
+where
+    TMsg: Message,
+{
+    /// Create a new [Router] without any routes or fallback
+    pub fn new() -> Self {
+        Self {
+            routes: vec![],
+            fallback: None,
+        }
+    }
+
+    /// Add a route, evaluated after all previously added routes
+    ///
+    /// * `predicate` - Selects the messages which this route forwards
+    /// * `target` - The actor to forward the selected messages to
+    ///
+    /// Returns the router, for chaining
+    pub fn add_route<F>(&mut self, predicate: F, target: ActorRef<TMsg>) -> &mut Self
+    where
+        F: Fn(&TMsg) -> bool + Send + Sync + 'static,
+    {
+        self.routes.push(Route {
+            predicate: Box::new(predicate),
+            target,
+        });
+        self
+    }
+
+    /// Set the fallback route, which receives messages no route accepted
+    ///
+    /// * `fallback` - The fallback actor, [None] to reject unmatched messages
+    ///
+    /// Returns the router, for chaining
+    pub fn set_fallback(&mut self, fallback: Option<ActorRef<TMsg>>) -> &mut Self {
+        self.fallback = fallback;
+        self
+    }
+
+    /// Remove the routes (and fallback) whose target has stopped
+    ///
+    /// Returns the number of routes removed, excluding the fallback
+    pub fn remove_stopped(&mut self) -> usize {
+        let before = self.routes.len();
+        self.routes
+            .retain(|route| ACTIVE_STATES.contains(&route.target.get_status()));
+        if let Some(fallback) = &self.fallback {
+            if !ACTIVE_STATES.contains(&fallback.get_status()) {
+                self.fallback = None;
+            }
+        }
+        before - self.routes.len()
+    }
+
+    /// Forward a message to the first matching route which accepts it, or to the fallback
+    ///
+    /// * `message` - The message to route
+    ///
+    /// Returns [Ok(ActorId)] with the identifier of the actor which received the message, or
+    /// [Err(MessagingErr)] if neither a matching route nor the fallback accepted it. Unless
+    /// the message was lost (e.g. with [MessagingErr::InvalidActorType]), the error holds the
+    /// message.
+    pub fn route(&self, message: TMsg) -> Result<ActorId, MessagingErr<TMsg>> {
+        let mut message = message;
+        for route in self.routes.iter() {
+            if !(route.predicate)(&message) || !route.target.is_marked_ready() {
+                continue;
+            }
+            match route.target.cast(message) {
+                Ok(()) => return Ok(route.target.get_id()),
+                // the target can't accept the message, fall through to the next route
+                Err(MessagingErr::SendErr(returned))
+                | Err(MessagingErr::MailboxBudgetExceeded(returned)) => message = returned,
+                Err(other) => return Err(other),
+            }
+        }
+        self.route_fallback(message)
+    }
+
+    /// Forward a copy of the message to every matching route which accepts it, or to the
+    /// fallback if none did
+    ///
+    /// * `message` - The message to route
+    ///
+    /// Returns [Ok(Vec<ActorId>)] with the identifiers of the actors which received the
+    /// message, or [Err(MessagingErr)] if neither a matching route nor the fallback
+    /// accepted it
+    pub fn route_all(&self, message: TMsg) -> Result<Vec<ActorId>, MessagingErr<TMsg>>
+    where
+        TMsg: Clone,
+    {
+        let recipients = self
+            .routes
+            .iter()
+            .filter(|route| (route.predicate)(&message) && route.target.is_marked_ready())
+            .filter(|route| route.target.cast(message.clone()).is_ok())
+            .map(|route| route.target.get_id())
+            .collect::<Vec<_>>();
+        if !recipients.is_empty() {
+            return Ok(recipients);
+        }
+        self.route_fallback(message).map(|id| vec![id])
+    }
+
+    /// Send a message which no route accepted to the fallback, if any
+    fn route_fallback(&self, message: TMsg) -> Result<ActorId, MessagingErr<TMsg>> {
+        match &self.fallback {
+            Some(fallback) if fallback.is_marked_ready() => {
+                fallback.cast(message).map(|_| fallback.get_id())
+            }
+            Some(_) => Err(MessagingErr::SendErr(message)),
+            None => Err(MessagingErr::SendErr(message)),
+        }
+    }
+}
@@ -0,0 +1,188 @@
+// Copyright (c) Sean Lawlor
+//
+// This source code is licensed under both the MIT license found in the
+// LICENSE-MIT file in the root directory of this source tree.
+
+//! Tests for content-based routing
+
+use std::sync::{Arc, Mutex};
+
+use crate::common_test::periodic_check;
+use crate::concurrency::{Duration, JoinHandle};
+use crate::{Actor, ActorProcessingErr, ActorRef, MessagingErr};
+
+use super::Router;
+
+/// An actor which records the messages it receives
+struct Recorder;
+
+#[cfg_attr(feature = "async-trait", crate::async_trait)]
+impl Actor for Recorder {
+    type Msg = u64;
+    type State = Arc<Mutex<Vec<u64>>>;
+    type Arguments = Arc<Mutex<Vec<u64>>>;
+
+    async fn pre_start(
+        &self,
+        _this_actor: ActorRef<Self::Msg>,
+        log: Arc<Mutex<Vec<u64>>>,
+    ) -> Result<Self::State, ActorProcessingErr> {
+        Ok(log)
+    }
+
+    async fn handle(
+        &self,
+        _this_actor: ActorRef<Self::Msg>,
+        message: Self::Msg,
+        log: &mut Self::State,
+    ) -> Result<(), ActorProcessingErr> {
+        log.lock().unwrap().push(message);
+        Ok(())
+    }
+}
+
+/// A [Recorder] which only becomes ready once it receives 0
+struct DeferredRecorder;
+
+#[cfg_attr(feature = "async-trait", crate::async_trait)]
+impl Actor for DeferredRecorder {
+    type Msg = u64;
+    type State = Arc<Mutex<Vec<u64>>>;
+    type Arguments = Arc<Mutex<Vec<u64>>>;
+
+    fn defer_readiness(&self) -> bool {
+        true
+    }
+
+    async fn pre_start(
+        &self,
+        _this_actor: ActorRef<Self::Msg>,
+        log: Arc<Mutex<Vec<u64>>>,
+    ) -> Result<Self::State, ActorProcessingErr> {
+        Ok(log)
+    }
+
+    async fn handle(
+        &self,
+        myself: ActorRef<Self::Msg>,
+        message: Self::Msg,
+        log: &mut Self::State,
+    ) -> Result<(), ActorProcessingErr> {
+        if message == 0 {
+            myself.mark_ready();
+        } else {
+            log.lock().unwrap().push(message);
+        }
+        Ok(())
+    }
+}
+
+async fn spawn_recorder() -> (ActorRef<u64>, JoinHandle<()>, Arc<Mutex<Vec<u64>>>) {
+    let log = Arc::new(Mutex::new(vec![]));
+    let (actor, handle) = Actor::spawn(None, Recorder, log.clone())
+        .await
+        .expect("Failed to start recorder");
+    (actor, handle, log)
+}
+
+#[crate::concurrency::test]
+#[tracing_test::traced_test]
+async fn test_router_first_match_and_fallback() {
+    let (evens, evens_handle, evens_log) = spawn_recorder().await;
+    let (small, small_handle, small_log) = spawn_recorder().await;
+    let (fallback, fallback_handle, fallback_log) = spawn_recorder().await;
+
+    let mut router = Router::new();
+    router
+        .add_route(|msg| msg % 2 == 0, evens.clone())
+        .add_route(|msg| *msg < 10, small.clone());
+
+    // without a fallback, unmatched messages are handed back
+    assert!(matches!(router.route(11), Err(MessagingErr::SendErr(11))));
+
+    router.set_fallback(Some(fallback.clone()));
+    // 2 matches both routes, but only the first receives it
+    assert_eq!(evens.get_id(), router.route(2).unwrap());
+    assert_eq!(small.get_id(), router.route(3).unwrap());
+    assert_eq!(fallback.get_id(), router.route(11).unwrap());
+
+    // copies go to every matching route
+    let recipients = router.route_all(4).unwrap();
+    assert_eq!(vec![evens.get_id(), small.get_id()], recipients);
+
+    // a dead target falls through to the next matching route
+    periodic_check(
+        || *evens_log.lock().unwrap() == vec![2, 4],
+        Duration::from_millis(500),
+    )
+    .await;
+    evens.stop(None);
+    evens_handle.await.unwrap();
+    assert_eq!(small.get_id(), router.route(6).unwrap());
+    assert_eq!(fallback.get_id(), router.route(12).unwrap());
+    assert_eq!(1, router.remove_stopped());
+
+    periodic_check(
+        || *small_log.lock().unwrap() == vec![3, 4, 6],
+        Duration::from_millis(500),
+    )
+    .await;
+    assert_eq!(vec![11, 12], *fallback_log.lock().unwrap());
+
+    // cleanup
+    small.stop(None);
+    fallback.stop(None);
+    small_handle.await.unwrap();
+    fallback_handle.await.unwrap();
+}
+
+#[crate::concurrency::test]
+#[tracing_test::traced_test]
+async fn test_router_skips_targets_which_arent_ready() {
+    let log = Arc::new(Mutex::new(vec![]));
+    let (warming, warming_handle) = Actor::spawn(None, DeferredRecorder, log.clone())
+        .await
+        .expect("Failed to start recorder");
+    let (ready, ready_handle, ready_log) = spawn_recorder().await;
+
+    let mut router = Router::new();
+    router
+        .add_route(|_| true, warming.clone())
+        .add_route(|_| true, ready.clone());
+
+    // the target which isn't ready yet is skipped, like a dead target
+    assert_eq!(ready.get_id(), router.route(1).unwrap());
+    assert_eq!(vec![ready.get_id()], router.route_all(2).unwrap());
+
+    // it receives messages once it's ready
+    warming.cast(0).expect("Failed to send to recorder");
+    periodic_check(|| warming.is_ready(), Duration::from_millis(500)).await;
+    assert_eq!(warming.get_id(), router.route(3).unwrap());
+
+    // a fallback which isn't ready doesn't receive unmatched messages either
+    let (fallback, fallback_handle) = Actor::spawn(None, DeferredRecorder, log.clone())
+        .await
+        .expect("Failed to start recorder");
+    let mut router = Router::new();
+    router.set_fallback(Some(fallback.clone()));
+    assert!(matches!(router.route(4), Err(MessagingErr::SendErr(4))));
+
+    periodic_check(
+        || *ready_log.lock().unwrap() == vec![1, 2],
+        Duration::from_millis(500),
+    )
+    .await;
+    periodic_check(
+        || *log.lock().unwrap() == vec![3],
+        Duration::from_millis(500),
+    )
+    .await;
+
+    // cleanup
+    warming.stop(None);
+    ready.stop(None);
+    fallback.stop(None);
+    warming_handle.await.unwrap();
+    ready_handle.await.unwrap();
+    fallback_handle.await.unwrap();
+}
@@ -151,6 +151,77 @@ where
     })
 }
 
+/// A handle to an in-flight [call_cancellable], which can either be awaited for the
+/// reply or cancelled
+#[derive(Debug)]
+pub struct CallHandle<TReply> {
+    rx: concurrency::OneshotReceiver<TReply>,
+    timeout: Option<Duration>,
+}
+
+impl<TReply> CallHandle<TReply> {
+    /// Wait for the reply to the call, up to the call's timeout (if any)
+    ///
+    /// Returns the [CallResult] of the call
+    pub async fn result(self) -> CallResult<TReply> {
+        if let Some(duration) = self.timeout {
+            match crate::concurrency::timeout(duration, self.rx).await {
+                Ok(Ok(result)) => CallResult::Success(result),
+                Ok(Err(_send_err)) => CallResult::SenderError,
+                Err(_timeout_err) => CallResult::Timeout,
+            }
+        } else {
+            match self.rx.await {
+                Ok(result) => CallResult::Success(result),
+                Err(_send_err) => CallResult::SenderError,
+            }
+        }
+    }
+
+    /// Cancel the call, dropping the reply channel. The callee is notified through
+    /// its [RpcReplyPort] (see [RpcReplyPort::cancelled] and [RpcReplyPort::is_closed])
+    /// and can abort any expensive work for the reply.
+    ///
+    /// NOTE: Cancellation is cooperative. If the handler doesn't observe the reply port's
+    /// cancellation, this only stops the caller from waiting and the handler still
+    /// runs to completion.
+    pub fn cancel(mut self) {
+        self.rx.close();
+    }
+}
+
+/// Sends an asynchronous request to the specified actor, building a one-time
+/// use reply channel, and returns a [CallHandle] to either await the reply
+/// or cancel the call
+///
+/// * `actor` - A reference to the [ActorCell] to communicate with
+/// * `msg_builder` - The [FnOnce] to construct the message
+/// * `timeout_option` - An optional [Duration] which represents the amount of
+///   time until the operation times out
+///
+/// Returns [Ok(CallHandle)] upon successful initial sending, [Err(MessagingErr)] if
+/// the initial send operation failed
+pub fn call_cancellable<TMessage, TReply, TMsgBuilder>(
+    actor: &ActorCell,
+    msg_builder: TMsgBuilder,
+    timeout_option: Option<Duration>,
+) -> Result<CallHandle<TReply>, MessagingErr<TMessage>>
+where
+    TMessage: Message,
+    TMsgBuilder: FnOnce(RpcReplyPort<TReply>) -> TMessage,
+{
+    let (tx, rx) = concurrency::oneshot();
+    let port: RpcReplyPort<TReply> = match timeout_option {
+        Some(duration) => (tx, duration).into(),
+        None => tx.into(),
+    };
+    actor.send_message::<TMessage>(msg_builder(port))?;
+    Ok(CallHandle {
+        rx,
+        timeout: timeout_option,
+    })
+}
+
 /// Sends an asynchronous request to the specified actors, building a one-time
 /// use reply channel for each actor and awaiting the results with the
 /// specified timeout
@@ -300,6 +371,18 @@ where
         call::<TMessage, TReply, TMsgBuilder>(&self.inner, msg_builder, timeout_option).await
     }
 
+    /// Alias of [call_cancellable]
+    pub fn call_cancellable<TReply, TMsgBuilder>(
+        &self,
+        msg_builder: TMsgBuilder,
+        timeout_option: Option<Duration>,
+    ) -> Result<CallHandle<TReply>, MessagingErr<TMessage>>
+    where
+        TMsgBuilder: FnOnce(RpcReplyPort<TReply>) -> TMessage,
+    {
+        call_cancellable::<TMessage, TReply, TMsgBuilder>(&self.inner, msg_builder, timeout_option)
+    }
+
     /// Alias of [call_and_forward]
     #[allow(clippy::type_complexity)]
     pub fn call_and_forward<TReply, TForwardMessage, TMsgBuilder, TFwdMessageBuilder>(
@@ -425,3 +425,100 @@ async fn test_multi_call() {
         handle.await.unwrap();
     }
 }
+
+#[crate::concurrency::test]
+#[tracing_test::traced_test]
+async fn test_rpc_call_cancellation() {
+    let finished = Arc::new(AtomicU8::new(0u8));
+    let short_circuited = Arc::new(AtomicU8::new(0u8));
+
+    struct TestActor {
+        finished: Arc<AtomicU8>,
+        short_circuited: Arc<AtomicU8>,
+    }
+
+    enum MessageFormat {
+        ExpensiveWork(crate::RpcReplyPort<u8>),
+    }
+    #[cfg(feature = "cluster")]
+    impl crate::Message for MessageFormat {}
+
+    #[cfg_attr(feature = "async-trait", crate::async_trait)]
+    impl Actor for TestActor {
+        type Msg = MessageFormat;
+        type Arguments = ();
+        type State = ();
+
+        async fn pre_start(
+            &self,
+            _this_actor: ActorRef<Self::Msg>,
+            _: (),
+        ) -> Result<Self::State, ActorProcessingErr> {
+            Ok(())
+        }
+
+        async fn handle(
+            &self,
+            _this_actor: ActorRef<Self::Msg>,
+            message: Self::Msg,
+            _state: &mut Self::State,
+        ) -> Result<(), ActorProcessingErr> {
+            match message {
+                MessageFormat::ExpensiveWork(mut reply) => {
+                    // race the "expensive work" against the caller cancelling
+                    match crate::concurrency::timeout(Duration::from_millis(200), reply.cancelled())
+                        .await
+                    {
+                        Ok(()) => {
+                            self.short_circuited.fetch_add(1, Ordering::Relaxed);
+                        }
+                        Err(_) => {
+                            self.finished.fetch_add(1, Ordering::Relaxed);
+                            let _ = reply.send(42);
+                        }
+                    }
+                }
+            }
+            Ok(())
+        }
+    }
+
+    let (actor, handle) = Actor::spawn(
+        None,
+        TestActor {
+            finished: finished.clone(),
+            short_circuited: short_circuited.clone(),
+        },
+        (),
+    )
+    .await
+    .expect("Failed to start test actor");
+
+    // an uncancelled call completes
+    let call = actor
+        .call_cancellable(MessageFormat::ExpensiveWork, None)
+        .expect("Failed to send call");
+    assert_eq!(42, call.result().await.expect("Call failed"));
+    assert_eq!(1, finished.load(Ordering::Relaxed));
+
+    // a cancelled call is observed by the callee, which short-circuits
+    let call = rpc::call_cancellable(
+        &actor.get_cell(),
+        MessageFormat::ExpensiveWork,
+        Some(Duration::from_millis(500)),
+    )
+    .expect("Failed to send call");
+    crate::concurrency::sleep(Duration::from_millis(10)).await;
+    call.cancel();
+
+    periodic_check(
+        || short_circuited.load(Ordering::Relaxed) == 1,
+        Duration::from_millis(100),
+    )
+    .await;
+    assert_eq!(1, finished.load(Ordering::Relaxed));
+
+    // cleanup
+    actor.stop(None);
+    handle.await.unwrap();
+}
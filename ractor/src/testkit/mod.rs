@@ -0,0 +1,207 @@
+// Copyright (c) Sean Lawlor
+//
+// This source code is licensed under both the MIT license found in the
+// LICENSE-MIT file in the root directory of this source tree.
+
+//! Utilities for testing actors
+//!
+//! Available with the `testkit` feature, which is meant to be enabled for tests only
+//! (e.g. through `[dev-dependencies]`).
+//!
+//! A [TestProbe] is a lightweight actor which records every message it receives, so that
+//! a test can hand its [ActorRef] to the actors under test and then assert on what they
+//! sent it.
+//!
+//! ```rust
+//! use ractor::concurrency::Duration;
+//! use ractor::testkit::TestProbe;
+//!
+//! #[derive(Debug, PartialEq)]
+//! enum Event {
+//!     Progress(u8),
+//!     Done,
+//! }
+//! # #[cfg(feature = "cluster")]
+//! # impl ractor::Message for Event {}
+//!
+//! async fn example() {
+//!     let probe = TestProbe::<Event>::spawn().await.expect("Failed to spawn probe");
+//!
+//!     // the actor under test would send these
+//!     probe.get_ref().cast(Event::Progress(50)).unwrap();
+//!     probe.get_ref().cast(Event::Done).unwrap();
+//!
+//!     // wait for the interesting message amid the noise
+//!     let done = probe
+//!         .expect(|msg| matches!(msg, Event::Done), Duration::from_millis(100))
+//!         .await;
+//!     assert_eq!(Some(Event::Done), done);
+//!
+//!     // the skipped progress message is still buffered
+//!     let progress = probe.expect_message(Duration::from_millis(100)).await;
+//!     assert_eq!(Some(Event::Progress(50)), progress);
+//! }
+//! ```
+//!
+//! ## Buffering
+//!
+//! Received messages are buffered in arrival order until an expectation consumes them. An
+//! expectation only consumes the message it returns: messages skipped over by a predicate
+//! stay in the buffer, in their original order, for later expectations.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use crate::concurrency::{Duration, Instant, Notify};
+use crate::{Actor, ActorProcessingErr, ActorRef, Message, SpawnErr};
+
+#[cfg(test)]
+mod tests;
+
+/// The messages received by a [TestProbe], shared between the probe's actor and its handle
+struct ProbeQueue<TMsg> {
+    messages: Mutex<VecDeque<TMsg>>,
+    arrived: Notify,
+}
+
+/// The actor backing a [TestProbe], which records every message it receives
+struct ProbeActor<TMsg> {
+    queue: Arc<ProbeQueue<TMsg>>,
+}
+
+#[cfg_attr(feature = "async-trait", crate::async_trait)]
+impl<TMsg> Actor for ProbeActor<TMsg>
+where
+    TMsg: Message,
+{
+    type Msg = TMsg;
+    type State = ();
+    type Arguments = ();
+
+    async fn pre_start(
+        &self,
+        _myself: ActorRef<Self::Msg>,
+        _: (),
+    ) -> Result<Self::State, ActorProcessingErr> {
+        Ok(())
+    }
+
+    async fn handle(
+        &self,
+        _myself: ActorRef<Self::Msg>,
+        message: Self::Msg,
+        _state: &mut Self::State,
+    ) -> Result<(), ActorProcessingErr> {
+        self.queue.messages.lock().unwrap().push_back(message);
+        self.queue.arrived.notify_waiters();
+        Ok(())
+    }
+}
+
+/// A probe actor which records the messages sent to it, for asserting on in tests. See the
+/// [module docs](self) for the buffering semantics.
+///
+/// The probe's actor is stopped when the probe is dropped.
+pub struct TestProbe<TMsg>
+where
+    TMsg: Message,
+{
+    actor: ActorRef<TMsg>,
+    queue: Arc<ProbeQueue<TMsg>>,
+}
+
+impl<TMsg> std::fmt::Debug for TestProbe<TMsg>
+where
+    TMsg: Message,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "TestProbe({:?})", self.actor)
+    }
+}
+
+impl<TMsg> TestProbe<TMsg>
+where
+    TMsg: Message,
+{
+    /// Spawn a new, unsupervised probe
+    ///
+    /// Returns [Ok(TestProbe)] with the running probe, or [Err(SpawnErr)] if its actor
+    /// failed to start
+    pub async fn spawn() -> Result<Self, SpawnErr> {
+        let queue = Arc::new(ProbeQueue {
+            messages: Mutex::new(VecDeque::new()),
+            arrived: Notify::new(),
+        });
+        let (actor, _handle) = Actor::spawn(
+            None,
+            ProbeActor {
+                queue: queue.clone(),
+            },
+            (),
+        )
+        .await?;
+        Ok(Self { actor, queue })
+    }
+
+    /// Retrieve the probe's [ActorRef], to give to the actors under test
+    pub fn get_ref(&self) -> &ActorRef<TMsg> {
+        &self.actor
+    }
+
+    /// Retrieve the number of received messages which haven't been consumed by an expectation
+    pub fn pending(&self) -> usize {
+        self.queue.messages.lock().unwrap().len()
+    }
+
+    /// Wait for the next message, in arrival order
+    ///
+    /// * `timeout` - The maximum time to wait for a message
+    ///
+    /// Returns [Some(TMsg)] with the consumed message, or [None] if nothing arrived in time
+    pub async fn expect_message(&self, timeout: Duration) -> Option<TMsg> {
+        self.expect(|_| true, timeout).await
+    }
+
+    /// Wait for the first message matching a predicate, checking already buffered messages
+    /// before waiting for new ones. Non-matching messages are skipped, but left in the buffer
+    /// for later expectations.
+    ///
+    /// * `predicate` - The condition the expected message satisfies
+    /// * `timeout` - The maximum time to wait for a matching message
+    ///
+    /// Returns [Some(TMsg)] with the consumed matching message, or [None] if no matching
+    /// message arrived in time
+    pub async fn expect<F>(&self, predicate: F, timeout: Duration) -> Option<TMsg>
+    where
+        F: Fn(&TMsg) -> bool,
+    {
+        let deadline = Instant::now() + timeout;
+        loop {
+            // register for arrivals before scanning, so one landing mid-scan isn't missed
+            let arrived = self.queue.arrived.notified();
+            {
+                let mut messages = self.queue.messages.lock().unwrap();
+                if let Some(index) = messages.iter().position(&predicate) {
+                    return messages.remove(index);
+                }
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero()
+                || crate::concurrency::timeout(remaining, arrived)
+                    .await
+                    .is_err()
+            {
+                return None;
+            }
+        }
+    }
+}
+
+impl<TMsg> Drop for TestProbe<TMsg>
+where
+    TMsg: Message,
+{
+    fn drop(&mut self) {
+        self.actor.stop(None);
+    }
+}
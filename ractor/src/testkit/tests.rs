@@ -0,0 +1,60 @@
+// Copyright (c) Sean Lawlor
+//
+// This source code is licensed under both the MIT license found in the
+// LICENSE-MIT file in the root directory of this source tree.
+
+//! Tests for the test probe
+
+use crate::concurrency::{sleep, Duration};
+
+use super::TestProbe;
+
+#[derive(Debug, PartialEq)]
+enum Event {
+    Noise(u64),
+    Interesting(u64),
+}
+#[cfg(feature = "cluster")]
+impl crate::Message for Event {}
+
+#[crate::concurrency::test]
+#[tracing_test::traced_test]
+async fn test_probe_expect_skips_and_buffers_non_matching_messages() {
+    let probe = TestProbe::<Event>::spawn()
+        .await
+        .expect("Failed to spawn probe");
+
+    let sender = probe.get_ref().clone();
+    crate::concurrency::spawn(async move {
+        sleep(Duration::from_millis(20)).await;
+        sender.cast(Event::Noise(1)).unwrap();
+        sender.cast(Event::Noise(2)).unwrap();
+        sender.cast(Event::Interesting(3)).unwrap();
+    });
+
+    // waits for the matching message to arrive, skipping the noise
+    let matched = probe
+        .expect(
+            |msg| matches!(msg, Event::Interesting(_)),
+            Duration::from_millis(500),
+        )
+        .await;
+    assert_eq!(Some(Event::Interesting(3)), matched);
+
+    // the skipped messages are left for later expectations, in arrival order
+    assert_eq!(2, probe.pending());
+    assert_eq!(
+        Some(Event::Noise(1)),
+        probe.expect_message(Duration::from_millis(100)).await
+    );
+    assert_eq!(
+        Some(Event::Noise(2)),
+        probe.expect_message(Duration::from_millis(100)).await
+    );
+
+    // nothing left, so the expectation times out
+    assert_eq!(
+        None,
+        probe.expect(|_| true, Duration::from_millis(50)).await
+    );
+}
@@ -0,0 +1,104 @@
+// Copyright (c) Sean Lawlor
+//
+// This source code is licensed under both the MIT license found in the
+// LICENSE-MIT file in the root directory of this source tree.
+
+//! Injectable clocks for the timer functions
+//!
+//! The timers in [crate::time] use the runtime's clock by default. The `*_with_clock`
+//! variants instead take a [Clock], which allows driving timer logic from a clock which
+//! is fully controlled by the caller (e.g. a [FakeClock] in unit tests), independent of
+//! the ambient runtime's notion of time.
+
+use std::sync::Mutex;
+
+use futures::future::BoxFuture;
+use futures::FutureExt;
+
+use crate::concurrency::{Duration, Instant, Notify};
+
+/// A source of time for the timer functions
+pub trait Clock: Send + Sync + 'static {
+    /// Retrieve the current point in time according to this clock
+    fn now(&self) -> Instant;
+
+    /// Sleep until the provided duration has elapsed on this clock
+    ///
+    /// * `dur` - The [Duration] to sleep for
+    fn sleep(&self, dur: Duration) -> BoxFuture<'_, ()>;
+}
+
+/// The default [Clock], which is backed by the runtime's clock (see [crate::concurrency])
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn sleep(&self, dur: Duration) -> BoxFuture<'_, ()> {
+        crate::concurrency::sleep(dur).boxed()
+    }
+}
+
+/// A manually driven [Clock]. Time only moves forward when [FakeClock::advance] is called,
+/// at which point any sleeps whose deadline has been reached will complete.
+#[derive(Debug)]
+pub struct FakeClock {
+    start: Instant,
+    elapsed: Mutex<Duration>,
+    notify: Notify,
+}
+
+impl Default for FakeClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FakeClock {
+    /// Create a new [FakeClock], with time frozen at the point of creation
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            elapsed: Mutex::new(Duration::ZERO),
+            notify: Notify::new(),
+        }
+    }
+
+    /// Move this clock forward, waking any sleepers whose deadline has passed
+    ///
+    /// * `dur` - The [Duration] to advance time by
+    pub fn advance(&self, dur: Duration) {
+        *self.elapsed.lock().unwrap() += dur;
+        self.notify.notify_waiters();
+    }
+
+    /// Retrieve the total amount of time this clock has been advanced by
+    pub fn elapsed(&self) -> Duration {
+        *self.elapsed.lock().unwrap()
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> Instant {
+        self.start + self.elapsed()
+    }
+
+    fn sleep(&self, dur: Duration) -> BoxFuture<'_, ()> {
+        let deadline = self.now() + dur;
+        async move {
+            loop {
+                // register for the notification before checking the time, so an
+                // advance between the check and the await isn't missed
+                let notified = self.notify.notified();
+                if self.now() >= deadline {
+                    return;
+                }
+                notified.await;
+            }
+        }
+        .boxed()
+    }
+}
@@ -82,10 +82,32 @@
 //!     handle.await.unwrap();
 //! }
 //! ```
+//!
+//! ## Restarted actors
+//!
+//! The timers above target a specific [ActorCell], so when a supervisor restarts a crashed
+//! actor the timers set up by the crashed instance end with it. The restarted instance is
+//! a new actor as far as the framework is concerned, since restarts are performed by the
+//! supervisor's own code (e.g. [crate::Actor::spawn_linked] in its supervision handler), so
+//! an actor which relies on periodic self-messaging should set its timers up in
+//! `post_start`, which every instance runs. Any state carried between ticks (e.g. a retry
+//! counter or backoff) that should survive a restart belongs with the supervisor, which can
+//! hand it to the restarted instance through its startup arguments.
+//!
+//! ## Controlling time
+//!
+//! Each timer has a `*_with_clock` variant which takes a [Clock] instead of relying on
+//! the runtime's clock. Supplying a [FakeClock] lets tests advance time explicitly and
+//! assert on exact tick counts, independent of the runtime.
+
+use std::sync::Arc;
 
 use crate::concurrency::{Duration, JoinHandle};
 
-use crate::{ActorCell, Message, MessagingErr, ACTIVE_STATES};
+use crate::{ActorCell, Message, MessagingErr, StopReason, ACTIVE_STATES};
+
+pub mod clock;
+pub use clock::{Clock, FakeClock, SystemClock};
 
 #[cfg(test)]
 mod tests;
@@ -153,7 +175,7 @@ where
 }
 
 /// Sends the stop signal to the actor after a specified duration, attaching a reason
-/// of "Exit after {}ms" by default
+/// of [StopReason::Shutdown] with the context "Exit after {}ms"
 ///
 /// * `period` - The [Duration] representing the time to delay before sending
 /// * `actor` - The [ActorCell] representing the [crate::Actor] to exit after the duration
@@ -163,7 +185,10 @@ where
 pub fn exit_after(period: Duration, actor: ActorCell) -> JoinHandle<()> {
     crate::concurrency::spawn(async move {
         crate::concurrency::sleep(period).await;
-        actor.stop(Some(format!("Exit after {}ms", period.as_millis())))
+        actor.stop_with_reason(Some(StopReason::Shutdown(Some(format!(
+            "Exit after {}ms",
+            period.as_millis()
+        )))))
     })
 }
 
@@ -181,6 +206,140 @@ pub fn kill_after(period: Duration, actor: ActorCell) -> JoinHandle<()> {
     })
 }
 
+/// Sends a message to a given actor repeatedly after a specified time, measuring
+/// time with the provided [Clock]. See [send_interval] for details.
+///
+/// Ticks are scheduled against fixed deadlines (`start + n * period`) so the period
+/// doesn't drift. If the clock jumps past several deadlines at once, a message is sent
+/// for each of them.
+///
+/// * `period` - The [Duration] representing the period for the send interval
+/// * `actor` - The [ActorCell] representing the [crate::Actor] to communicate with
+/// * `msg` - The [Fn] message builder which is called to generate a message for each send
+///   operation.
+/// * `clock` - The [Clock] to measure time with
+///
+/// Returns: The [JoinHandle] which represents the backgrounded work (can be ignored to
+/// "fire and forget")
+pub fn send_interval_with_clock<TMessage, F>(
+    period: Duration,
+    actor: ActorCell,
+    msg: F,
+    clock: Arc<dyn Clock>,
+) -> JoinHandle<()>
+where
+    TMessage: Message,
+    F: Fn() -> TMessage + Send + 'static,
+{
+    // capture the start before spawning, so the schedule doesn't depend on when the task
+    // is first polled
+    let start = clock.now();
+    crate::concurrency::spawn(async move {
+        let mut next_tick = start + period;
+        while ACTIVE_STATES.contains(&actor.get_status()) {
+            let now = clock.now();
+            if next_tick > now {
+                clock.sleep(next_tick - now).await;
+            }
+            next_tick += period;
+            // if we receive an error trying to send, the channel is closed and we should stop trying
+            // actor died
+            if actor.send_message::<TMessage>(msg()).is_err() {
+                break;
+            }
+        }
+    })
+}
+
+/// Sends a message after a given period to the specified actor, measuring time with
+/// the provided [Clock]. See [send_after] for details.
+///
+/// * `period` - The [Duration] representing the time to delay before sending
+/// * `actor` - The [ActorCell] representing the [crate::Actor] to communicate with
+/// * `msg` - The [FnOnce] message builder which is called to generate a message for the send
+///   operation
+/// * `clock` - The [Clock] to measure time with
+///
+/// Returns: The [JoinHandle<Result<(), MessagingErr>>] which represents the backgrounded work.
+/// Awaiting the handle will yield the result of the send operation. Can be safely ignored to
+/// "fire and forget"
+pub fn send_after_with_clock<TMessage, F>(
+    period: Duration,
+    actor: ActorCell,
+    msg: F,
+    clock: Arc<dyn Clock>,
+) -> JoinHandle<Result<(), MessagingErr<TMessage>>>
+where
+    TMessage: Message,
+    F: FnOnce() -> TMessage + Send + 'static,
+{
+    let sleep = sleep_until(clock, period);
+    crate::concurrency::spawn(async move {
+        sleep.await;
+        actor.send_message::<TMessage>(msg())
+    })
+}
+
+/// Sends the stop signal to the actor after a specified duration, measuring time with
+/// the provided [Clock]. See [exit_after] for details.
+///
+/// * `period` - The [Duration] representing the time to delay before sending
+/// * `actor` - The [ActorCell] representing the [crate::Actor] to exit after the duration
+/// * `clock` - The [Clock] to measure time with
+///
+/// Returns: The [JoinHandle] which denotes the backgrounded operation. To cancel the
+/// exit operation, you can abort the handle
+pub fn exit_after_with_clock(
+    period: Duration,
+    actor: ActorCell,
+    clock: Arc<dyn Clock>,
+) -> JoinHandle<()> {
+    let sleep = sleep_until(clock, period);
+    crate::concurrency::spawn(async move {
+        sleep.await;
+        actor.stop_with_reason(Some(StopReason::Shutdown(Some(format!(
+            "Exit after {}ms",
+            period.as_millis()
+        )))))
+    })
+}
+
+/// Sends the KILL signal to the actor after a specified duration, measuring time with
+/// the provided [Clock]. See [kill_after] for details.
+///
+/// * `period` - The [Duration] representing the time to delay before sending
+/// * `actor` - The [ActorCell] representing the [crate::Actor] to kill after the duration
+/// * `clock` - The [Clock] to measure time with
+///
+/// Returns: The [JoinHandle] which denotes the backgrounded operation. To cancel the
+/// kill operation, you can abort the handle
+pub fn kill_after_with_clock(
+    period: Duration,
+    actor: ActorCell,
+    clock: Arc<dyn Clock>,
+) -> JoinHandle<()> {
+    let sleep = sleep_until(clock, period);
+    crate::concurrency::spawn(async move {
+        sleep.await;
+        actor.kill()
+    })
+}
+
+/// Build a future which completes once `period` has elapsed on the clock, measured
+/// from the moment of this call rather than when the future is first polled
+fn sleep_until(
+    clock: Arc<dyn Clock>,
+    period: Duration,
+) -> impl std::future::Future<Output = ()> + Send {
+    let deadline = clock.now() + period;
+    async move {
+        let now = clock.now();
+        if deadline > now {
+            clock.sleep(deadline - now).await;
+        }
+    }
+}
+
 /// Add the timing functionality on top of the [crate::ActorRef]
 impl<TMessage> crate::ActorRef<TMessage>
 where
@@ -215,4 +374,40 @@ where
     pub fn kill_after(&self, period: Duration) -> JoinHandle<()> {
         kill_after(period, self.get_cell())
     }
+
+    /// Alias of [send_interval_with_clock]
+    pub fn send_interval_with_clock<F>(
+        &self,
+        period: Duration,
+        msg: F,
+        clock: Arc<dyn Clock>,
+    ) -> JoinHandle<()>
+    where
+        F: Fn() -> TMessage + Send + 'static,
+    {
+        send_interval_with_clock::<TMessage, F>(period, self.get_cell(), msg, clock)
+    }
+
+    /// Alias of [send_after_with_clock]
+    pub fn send_after_with_clock<F>(
+        &self,
+        period: Duration,
+        msg: F,
+        clock: Arc<dyn Clock>,
+    ) -> JoinHandle<Result<(), MessagingErr<TMessage>>>
+    where
+        F: FnOnce() -> TMessage + Send + 'static,
+    {
+        send_after_with_clock::<TMessage, F>(period, self.get_cell(), msg, clock)
+    }
+
+    /// Alias of [exit_after_with_clock]
+    pub fn exit_after_with_clock(&self, period: Duration, clock: Arc<dyn Clock>) -> JoinHandle<()> {
+        exit_after_with_clock(period, self.get_cell(), clock)
+    }
+
+    /// Alias of [kill_after_with_clock]
+    pub fn kill_after_with_clock(&self, period: Duration, clock: Arc<dyn Clock>) -> JoinHandle<()> {
+        kill_after_with_clock(period, self.get_cell(), clock)
+    }
 }
@@ -12,7 +12,37 @@ use std::sync::{
 
 use crate::{common_test::periodic_check, concurrency::Duration, ActorProcessingErr};
 
-use crate::{Actor, ActorRef};
+use crate::{Actor, ActorRef, ActorStatus};
+
+use super::FakeClock;
+
+/// An actor which counts the messages it receives
+struct CounterActor {
+    counter: Arc<AtomicU8>,
+}
+
+#[cfg_attr(feature = "async-trait", crate::async_trait)]
+impl Actor for CounterActor {
+    type Msg = ();
+    type State = ();
+    type Arguments = ();
+    async fn pre_start(
+        &self,
+        _this_actor: ActorRef<Self::Msg>,
+        _: (),
+    ) -> Result<Self::State, ActorProcessingErr> {
+        Ok(())
+    }
+    async fn handle(
+        &self,
+        _this_actor: ActorRef<Self::Msg>,
+        _message: Self::Msg,
+        _state: &mut Self::State,
+    ) -> Result<(), ActorProcessingErr> {
+        self.counter.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+}
 
 #[crate::concurrency::test]
 #[tracing_test::traced_test]
@@ -221,3 +251,139 @@ async fn test_kill_after() {
     )
     .await;
 }
+
+#[crate::concurrency::test]
+#[tracing_test::traced_test]
+async fn test_intervals_with_fake_clock() {
+    let counter = Arc::new(AtomicU8::new(0u8));
+    let (actor_ref, actor_handle) = Actor::spawn(
+        None,
+        CounterActor {
+            counter: counter.clone(),
+        },
+        (),
+    )
+    .await
+    .expect("Failed to create test actor");
+
+    let clock = Arc::new(FakeClock::new());
+    let interval_handle =
+        actor_ref.send_interval_with_clock(Duration::from_millis(10), || (), clock.clone());
+
+    // real time passing doesn't tick the timer
+    crate::concurrency::sleep(Duration::from_millis(50)).await;
+    assert_eq!(0, counter.load(Ordering::Relaxed));
+
+    for i in 1..=5u8 {
+        clock.advance(Duration::from_millis(10));
+        periodic_check(
+            || counter.load(Ordering::Relaxed) == i,
+            Duration::from_millis(500),
+        )
+        .await;
+    }
+
+    // advancing part of a period doesn't tick
+    clock.advance(Duration::from_millis(5));
+    crate::concurrency::sleep(Duration::from_millis(50)).await;
+    assert_eq!(5, counter.load(Ordering::Relaxed));
+
+    // jumping over multiple deadlines ticks once for each of them
+    clock.advance(Duration::from_millis(25));
+    periodic_check(
+        || counter.load(Ordering::Relaxed) == 8,
+        Duration::from_millis(500),
+    )
+    .await;
+    crate::concurrency::sleep(Duration::from_millis(50)).await;
+    assert_eq!(8, counter.load(Ordering::Relaxed));
+
+    actor_ref.stop(None);
+    actor_handle.await.unwrap();
+    // the timer exits on its next tick once the actor is gone
+    clock.advance(Duration::from_millis(10));
+    periodic_check(|| interval_handle.is_finished(), Duration::from_millis(500)).await;
+    assert_eq!(8, counter.load(Ordering::Relaxed));
+}
+
+#[crate::concurrency::test]
+#[tracing_test::traced_test]
+async fn test_send_after_with_fake_clock() {
+    let counter = Arc::new(AtomicU8::new(0u8));
+    let (actor_ref, actor_handle) = Actor::spawn(
+        None,
+        CounterActor {
+            counter: counter.clone(),
+        },
+        (),
+    )
+    .await
+    .expect("Failed to create test actor");
+
+    let clock = Arc::new(FakeClock::new());
+    let send_after_handle =
+        actor_ref.send_after_with_clock(Duration::from_millis(100), || (), clock.clone());
+
+    clock.advance(Duration::from_millis(99));
+    crate::concurrency::sleep(Duration::from_millis(50)).await;
+    assert!(!send_after_handle.is_finished());
+    assert_eq!(0, counter.load(Ordering::Relaxed));
+
+    clock.advance(Duration::from_millis(1));
+    assert!(send_after_handle.await.unwrap().is_ok());
+    periodic_check(
+        || counter.load(Ordering::Relaxed) == 1,
+        Duration::from_millis(500),
+    )
+    .await;
+
+    actor_ref.stop(None);
+    actor_handle.await.unwrap();
+}
+
+#[crate::concurrency::test]
+#[tracing_test::traced_test]
+async fn test_exit_and_kill_after_with_fake_clock() {
+    let clock = Arc::new(FakeClock::new());
+
+    let (exit_ref, exit_actor_handle) = Actor::spawn(
+        None,
+        CounterActor {
+            counter: Arc::new(AtomicU8::new(0u8)),
+        },
+        (),
+    )
+    .await
+    .expect("Failed to create test actor");
+    let (kill_ref, kill_actor_handle) = Actor::spawn(
+        None,
+        CounterActor {
+            counter: Arc::new(AtomicU8::new(0u8)),
+        },
+        (),
+    )
+    .await
+    .expect("Failed to create test actor");
+
+    let exit_handle = exit_ref.exit_after_with_clock(Duration::from_millis(10), clock.clone());
+    let kill_handle = kill_ref.kill_after_with_clock(Duration::from_millis(20), clock.clone());
+
+    crate::concurrency::sleep(Duration::from_millis(50)).await;
+    assert_eq!(ActorStatus::Running, exit_ref.get_status());
+    assert_eq!(ActorStatus::Running, kill_ref.get_status());
+
+    clock.advance(Duration::from_millis(10));
+    periodic_check(
+        || exit_handle.is_finished() && exit_actor_handle.is_finished(),
+        Duration::from_millis(500),
+    )
+    .await;
+    assert_eq!(ActorStatus::Running, kill_ref.get_status());
+
+    clock.advance(Duration::from_millis(10));
+    periodic_check(
+        || kill_handle.is_finished() && kill_actor_handle.is_finished(),
+        Duration::from_millis(500),
+    )
+    .await;
+}
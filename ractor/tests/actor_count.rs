@@ -0,0 +1,146 @@
+// Copyright (c) Sean Lawlor
+//
+// This source code is licensed under both the MIT license found in the
+// LICENSE-MIT file in the root directory of this source tree.
+
+//! Tests of the process-wide actor count. These live in their own test binary (and
+//! therefore process) since the count and its cap are global, and the crate's unit tests
+//! spawn actors concurrently.
+
+use ractor::concurrency::{sleep, Duration, Instant};
+use ractor::{current_actor_count, set_max_actors, Actor, ActorProcessingErr, ActorRef, SpawnErr};
+use serial_test::serial;
+
+struct TestActor;
+
+#[cfg_attr(feature = "async-trait", ractor::async_trait)]
+impl Actor for TestActor {
+    type Msg = ();
+    type State = ();
+    type Arguments = ();
+
+    async fn pre_start(
+        &self,
+        _this_actor: ActorRef<Self::Msg>,
+        _: (),
+    ) -> Result<Self::State, ActorProcessingErr> {
+        Ok(())
+    }
+}
+
+struct FailingActor;
+
+#[cfg_attr(feature = "async-trait", ractor::async_trait)]
+impl Actor for FailingActor {
+    type Msg = ();
+    type State = ();
+    type Arguments = ();
+
+    async fn pre_start(
+        &self,
+        _this_actor: ActorRef<Self::Msg>,
+        _: (),
+    ) -> Result<Self::State, ActorProcessingErr> {
+        Err(From::from("Boom"))
+    }
+}
+
+/// Wait for the actor count to reach the expected value, panicking after the timeout
+async fn wait_for_count(expected: usize) {
+    let deadline = Instant::now() + Duration::from_millis(500);
+    while current_actor_count() != expected {
+        if Instant::now() > deadline {
+            panic!(
+                "Expected {expected} live actors, but there are {}",
+                current_actor_count()
+            );
+        }
+        sleep(Duration::from_millis(5)).await;
+    }
+}
+
+#[ractor::concurrency::test]
+#[serial]
+async fn spawned_actors_are_counted_until_stopped() {
+    let baseline = current_actor_count();
+
+    let (a, a_handle) = Actor::spawn(None, TestActor, ())
+        .await
+        .expect("Failed to spawn actor");
+    let (b, b_handle) = Actor::spawn(None, TestActor, ())
+        .await
+        .expect("Failed to spawn actor");
+    assert_eq!(baseline + 2, current_actor_count());
+
+    // released once the actor stops, while references to it are still held
+    a.stop(None);
+    a_handle.await.unwrap();
+    assert_eq!(baseline + 1, current_actor_count());
+
+    // dropping the last reference to a stopped actor doesn't release it again
+    drop(a);
+    wait_for_count(baseline + 1).await;
+    sleep(Duration::from_millis(20)).await;
+    assert_eq!(baseline + 1, current_actor_count());
+
+    b.stop(None);
+    b_handle.await.unwrap();
+    drop(b);
+    assert_eq!(baseline, current_actor_count());
+}
+
+#[ractor::concurrency::test]
+#[serial]
+async fn spawning_fails_at_the_cap() {
+    let baseline = current_actor_count();
+    set_max_actors(Some(baseline + 1));
+
+    let (actor, handle) = Actor::spawn(None, TestActor, ())
+        .await
+        .expect("Failed to spawn actor");
+    match Actor::spawn(None, TestActor, ()).await {
+        Err(SpawnErr::ActorLimitReached(max)) => assert_eq!(baseline + 1, max),
+        Err(other) => panic!("Expected the actor limit to be reached, got {other}"),
+        Ok(_) => panic!("Expected the actor limit to be reached"),
+    }
+    assert_eq!(baseline + 1, current_actor_count());
+
+    // stopping an actor frees its slot
+    actor.stop(None);
+    handle.await.unwrap();
+    let (actor, handle) = Actor::spawn(None, TestActor, ())
+        .await
+        .expect("Failed to spawn actor");
+
+    // cleanup
+    set_max_actors(None);
+    actor.stop(None);
+    handle.await.unwrap();
+    assert_eq!(baseline, current_actor_count());
+}
+
+#[ractor::concurrency::test]
+#[serial]
+async fn failed_spawns_dont_leak_slots() {
+    let baseline = current_actor_count();
+
+    // the name registration fails after the slot was acquired
+    let (actor, handle) = Actor::spawn(Some("counted_actor".to_string()), TestActor, ())
+        .await
+        .expect("Failed to spawn actor");
+    match Actor::spawn(Some("counted_actor".to_string()), TestActor, ()).await {
+        Err(SpawnErr::ActorAlreadyRegistered(name)) => assert_eq!("counted_actor", name),
+        Err(other) => panic!("Expected the name to be taken, got {other}"),
+        Ok(_) => panic!("Expected the name to be taken"),
+    }
+    assert_eq!(baseline + 1, current_actor_count());
+
+    // the actor fails to start
+    assert!(Actor::spawn(None, FailingActor, ()).await.is_err());
+    wait_for_count(baseline + 1).await;
+
+    // cleanup
+    actor.stop(None);
+    handle.await.unwrap();
+    assert_eq!(baseline, current_actor_count());
+}